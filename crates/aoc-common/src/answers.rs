@@ -0,0 +1,73 @@
+use std::{fs, io, path::Path};
+
+use crate::{ParseError, Part, kv};
+
+/// Confirmed answers for a day's `input.txt`, stored next to it as
+/// `answers.toml`:
+///
+/// ```toml
+/// part_1 = 1152
+/// part_2 = 6671
+/// ```
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_1: Option<u64>,
+    pub part_2: Option<u64>,
+}
+
+impl Answers {
+    pub fn get(&self, part: Part) -> Option<u64> {
+        match part {
+            Part::One => self.part_1,
+            Part::Two => self.part_2,
+        }
+    }
+
+    /// Loads `path`, treating a missing file as having no known answers.
+    pub fn load(path: &Path) -> Result<Answers, String> {
+        match fs::read_to_string(path) {
+            Ok(text) => Answers::parse(&text).map_err(|e| format!("{}: {e}", path.display())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(format!("{}: {e}", path.display())),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Answers, ParseError> {
+        let mut answers = Answers::default();
+        for entry in kv::entries(text)? {
+            let slot = match entry.key {
+                "part_1" => &mut answers.part_1,
+                "part_2" => &mut answers.part_2,
+                _ => return Err(entry.unknown_key("`part_1` or `part_2`")),
+            };
+            *slot = Some(entry.number("an answer")?);
+        }
+        Ok(answers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = Answers::parse("# confirmed\npart_1 = 1152\n\npart_2=6671 # late\n");
+        assert_eq!(
+            answers.unwrap(),
+            Answers {
+                part_1: Some(1152),
+                part_2: Some(6671),
+            }
+        );
+        assert_eq!(Answers::parse("part_2 = 7").unwrap().part_1, None);
+    }
+
+    #[test]
+    fn test_parse_answers_errors() {
+        let err = Answers::parse("part_1 = 1\npart_3 = 2").unwrap_err();
+        assert_eq!(err.position(), Some((2, 1)));
+        let err = Answers::parse("part_1 =  12x").unwrap_err();
+        assert_eq!(err.position(), Some((1, 11)));
+    }
+}
@@ -0,0 +1,149 @@
+//! Union-find over the elements `0..n`.
+
+/// Disjoint sets with path compression and union by size, so `find` and
+/// `union` run in amortised near-constant time.
+#[derive(Debug, Clone)]
+pub struct DisjointSetUnion {
+    parent: Vec<usize>,
+    /// Component size, kept up to date for roots only.
+    size: Vec<usize>,
+    components: usize,
+}
+
+impl DisjointSetUnion {
+    /// `n` singleton sets.
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            components: n,
+        }
+    }
+
+    /// Number of elements across all sets.
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Number of disjoint sets.
+    pub fn component_count(&self) -> usize {
+        self.components
+    }
+
+    /// The representative of `x`'s set. Iterative, so long chains can't
+    /// overflow the stack, and every element visited is pointed straight
+    /// at the root afterwards.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut current = x;
+        while self.parent[current] != root {
+            let next = self.parent[current];
+            self.parent[current] = root;
+            current = next;
+        }
+        root
+    }
+
+    /// Merges the sets containing `a` and `b`, attaching the smaller under
+    /// the larger. Returns `false` if they were already the same set.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        let (large, small) = if self.size[a] < self.size[b] {
+            (b, a)
+        } else {
+            (a, b)
+        };
+        self.parent[small] = large;
+        self.size[large] += self.size[small];
+        self.components -= 1;
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Size of the set containing `x`.
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// The size of every set, in no particular order.
+    pub fn component_sizes(&self) -> impl Iterator<Item = usize> + '_ {
+        self.parent
+            .iter()
+            .enumerate()
+            .filter(|&(i, &parent)| i == parent)
+            .map(|(root, _)| self.size[root])
+    }
+
+    /// The members of every set, each in increasing order.
+    pub fn components(&mut self) -> impl Iterator<Item = Vec<usize>> + use<> {
+        let mut members = vec![Vec::new(); self.len()];
+        for x in 0..self.len() {
+            let root = self.find(x);
+            members[root].push(x);
+        }
+        members.into_iter().filter(|members| !members.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_union_and_sizes() {
+        let mut sets = DisjointSetUnion::new(6);
+        assert_eq!(sets.component_count(), 6);
+        assert!(sets.union(0, 1));
+        assert!(sets.union(2, 3));
+        assert!(sets.union(1, 3));
+        assert!(!sets.union(0, 2));
+        assert!(sets.connected(0, 3));
+        assert!(!sets.connected(0, 4));
+        assert_eq!(sets.size(2), 4);
+        assert_eq!(sets.size(5), 1);
+        assert_eq!(sets.component_count(), 3);
+        let mut sizes: Vec<usize> = sets.component_sizes().collect();
+        sizes.sort();
+        assert_eq!(sizes, vec![1, 1, 4]);
+    }
+
+    #[test]
+    fn test_components() {
+        let mut sets = DisjointSetUnion::new(5);
+        sets.union(4, 0);
+        sets.union(1, 3);
+        let mut components: Vec<Vec<usize>> = sets.components().collect();
+        components.sort();
+        assert_eq!(components, vec![vec![0, 4], vec![1, 3], vec![2]]);
+    }
+
+    #[test]
+    fn test_long_chain_does_not_overflow() {
+        let n = 1_000_000;
+        let mut sets = DisjointSetUnion::new(n);
+        // Union by size never builds a chain this deep, so make one by hand.
+        for i in 1..n {
+            sets.parent[i] = i - 1;
+        }
+        sets.size[0] = n;
+        sets.components = 1;
+        assert_eq!(sets.find(n - 1), 0);
+        assert_eq!(sets.parent[n - 1], 0);
+        assert_eq!(sets.parent[n / 2], 0);
+        assert_eq!(sets.size(n - 1), n);
+    }
+}
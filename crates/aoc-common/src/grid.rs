@@ -0,0 +1,270 @@
+//! A rectangular 2D grid stored row-major, addressed by `(row, column)`.
+
+use std::{
+    fmt::{self, Display},
+    ops::{Index, IndexMut},
+};
+
+use crate::{ParseError, parse};
+
+/// A `(row, column)` position, both counted from 0 at the top left.
+pub type Position = (usize, usize);
+
+const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+const SURROUNDING: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    height: usize,
+    width: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(height: usize, width: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            height,
+            width,
+            cells: vec![fill; height * width],
+        }
+    }
+
+    /// Parses a map with one character per cell and one line per row.
+    /// `cell` turns each character into a cell, or returns what it expected
+    /// to find, which is reported at that character's line and column.
+    pub fn parse<E: Into<String>>(
+        text: &str,
+        mut cell: impl FnMut(Position, char) -> Result<T, E>,
+    ) -> Result<Self, ParseError> {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        for (line_number, line) in parse::numbered_lines(text) {
+            let start = cells.len();
+            for (col, c) in line.chars().enumerate() {
+                let value = cell((height, col), c)
+                    .map_err(|expected| ParseError::new(line_number, col + 1, c, expected))?;
+                cells.push(value);
+            }
+            let length = cells.len() - start;
+            let width = *width.get_or_insert(length);
+            if length == 0 || length != width {
+                let found = line.chars().skip(width).collect::<String>();
+                let expected = format!("a row {} wide", width.max(1));
+                return Err(ParseError::new(
+                    line_number,
+                    length.min(width) + 1,
+                    found,
+                    expected,
+                ));
+            }
+            height += 1;
+        }
+        let Some(width) = width else {
+            return Err(ParseError::new(1, 1, "", "a row of the grid"));
+        };
+        Ok(Self {
+            height,
+            width,
+            cells,
+        })
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn get(&self, (row, col): Position) -> Option<&T> {
+        (row < self.height && col < self.width).then(|| &self.cells[row * self.width + col])
+    }
+
+    pub fn get_mut(&mut self, (row, col): Position) -> Option<&mut T> {
+        (row < self.height && col < self.width).then(|| &mut self.cells[row * self.width + col])
+    }
+
+    /// `position` moved by a signed `(rows, columns)` offset, if that is
+    /// still inside the grid.
+    pub fn offset(&self, (row, col): Position, (d_row, d_col): (isize, isize)) -> Option<Position> {
+        let row = row
+            .checked_add_signed(d_row)
+            .filter(|&row| row < self.height)?;
+        let col = col
+            .checked_add_signed(d_col)
+            .filter(|&col| col < self.width)?;
+        Some((row, col))
+    }
+
+    /// The up to 4 positions sharing an edge with `position`.
+    pub fn neighbours_4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        ORTHOGONAL
+            .into_iter()
+            .filter_map(move |delta| self.offset(position, delta))
+    }
+
+    /// The up to 8 positions sharing an edge or a corner with `position`.
+    pub fn neighbours_8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        SURROUNDING
+            .into_iter()
+            .filter_map(move |delta| self.offset(position, delta))
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Position> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // Not `chunks(width)`, which panics for a grid with no columns.
+        (0..self.height).map(|row| self.row(row))
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.width, "column {col} out of bounds");
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            height: self.height,
+            width: self.width,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Sets the region of cells equal to the one at `start`, connected
+    /// through shared edges, to `value`. Returns how many cells changed.
+    pub fn flood_fill(&mut self, start: Position, value: T) -> usize
+    where
+        T: Clone + PartialEq,
+    {
+        let target = self[start].clone();
+        if target == value {
+            return 0;
+        }
+        let mut filled = 0;
+        let mut stack = vec![start];
+        while let Some(position) = stack.pop() {
+            if self[position] != target {
+                continue;
+            }
+            self[position] = value.clone();
+            filled += 1;
+            stack.extend(
+                self.neighbours_4(position)
+                    .filter(|&neighbour| self[neighbour] == target),
+            );
+        }
+        filled
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(position)
+            .unwrap_or_else(|| panic!("{position:?} out of bounds"))
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("{position:?} out of bounds"))
+    }
+}
+
+/// Prints each row on its own line with no separator between cells.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(text: &str) -> Grid<u32> {
+        Grid::parse(text, |_, c| c.to_digit(10).ok_or("a digit")).unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_index() {
+        let grid = digits("123\n456\n");
+        assert_eq!((grid.height(), grid.width()), (2, 3));
+        assert_eq!(grid[(1, 0)], 4);
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.column(2).collect::<Vec<_>>(), vec![&3, &6]);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.to_string(), "123\n456\n");
+    }
+
+    #[test]
+    fn test_empty_rows() {
+        let grid = Grid::new(3, 0, 0u8);
+        assert_eq!(grid.rows().count(), 3);
+        assert!(grid.rows().all(<[u8]>::is_empty));
+        assert_eq!(grid.positions().count(), 0);
+        assert_eq!(grid.to_string(), "\n\n\n");
+        assert_eq!(Grid::new(0, 4, 0u8).rows().count(), 0);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let parse = |text| Grid::parse(text, |_, c| c.to_digit(10).ok_or("a digit"));
+        assert_eq!(parse("12\n3x").unwrap_err().position(), Some((2, 2)));
+        assert_eq!(parse("12\n345").unwrap_err().position(), Some((2, 3)));
+        assert_eq!(parse("12\n").unwrap().height(), 1);
+        assert_eq!(parse("").unwrap_err().position(), Some((1, 1)));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = digits("123\n456\n789");
+        let mut corner: Vec<_> = grid.neighbours_8((0, 0)).collect();
+        corner.sort();
+        assert_eq!(corner, vec![(0, 1), (1, 0), (1, 1)]);
+        assert_eq!(grid.neighbours_4((1, 1)).count(), 4);
+        assert_eq!(grid.neighbours_8((1, 1)).count(), 8);
+        assert_eq!(grid.offset((0, 2), (0, 1)), None);
+        assert_eq!(grid.offset((0, 2), (2, -2)), Some((2, 0)));
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let mut grid = digits("0010\n0110\n1000");
+        assert_eq!(grid.flood_fill((0, 0), 2), 3);
+        assert_eq!(grid.to_string(), "2210\n2110\n1000\n");
+        assert_eq!(grid.flood_fill((2, 3), 0), 0);
+        assert_eq!(grid.flood_fill((0, 3), 3), 5);
+    }
+}
@@ -0,0 +1,91 @@
+use std::str::FromStr;
+
+use crate::{ParseError, parse};
+
+/// One `key = value` line of the small TOML subset our config files use:
+/// bare keys, integer or double-quoted string values and `#` comments.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Entry<'a> {
+    pub line: usize,
+    pub key: &'a str,
+    pub key_column: usize,
+    pub value: &'a str,
+    pub value_column: usize,
+}
+
+impl Entry<'_> {
+    pub fn number<T: FromStr>(&self, expected: &str) -> Result<T, ParseError> {
+        parse::field(self.line, self.value_column, self.value, expected)
+    }
+
+    pub fn string(&self) -> Result<String, ParseError> {
+        self.value
+            .strip_prefix('"')
+            .and_then(|rest| rest.strip_suffix('"'))
+            .map(String::from)
+            .ok_or_else(|| {
+                ParseError::new(self.line, self.value_column, self.value, "a quoted string")
+            })
+    }
+
+    pub fn unknown_key(&self, expected: &str) -> ParseError {
+        ParseError::new(self.line, self.key_column, self.key, expected)
+    }
+}
+
+pub fn entries(text: &str) -> Result<Vec<Entry<'_>>, ParseError> {
+    let mut entries = Vec::new();
+    for (line_number, line) in parse::numbered_lines(text) {
+        let content = strip_comment(line);
+        if content.trim().is_empty() {
+            continue;
+        }
+        let Some((key, value)) = content.split_once('=') else {
+            return Err(ParseError::new(line_number, 1, line, "`key = value`"));
+        };
+        let value_offset = key.len() + 1 + (value.len() - value.trim_start().len());
+        entries.push(Entry {
+            line: line_number,
+            key: key.trim(),
+            key_column: key.len() - key.trim_start().len() + 1,
+            value: value.trim(),
+            value_column: line[..value_offset].chars().count() + 1,
+        });
+    }
+    Ok(entries)
+}
+
+/// Drops a trailing `#` comment, leaving `#` inside quoted strings alone.
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entries() {
+        let entries = entries("# header\n  part_1 =  12 # note\n\nsession = \"a#b\"\n").unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!((entries[0].key, entries[0].key_column), ("part_1", 3));
+        assert_eq!((entries[0].value, entries[0].value_column), ("12", 13));
+        assert_eq!(entries[0].number::<u64>("an answer").unwrap(), 12);
+        assert_eq!(entries[1].string().unwrap(), "a#b");
+        assert!(entries[0].string().is_err());
+    }
+
+    #[test]
+    fn test_entries_without_equals() {
+        let err = entries("part_1 = 1\noops").unwrap_err();
+        assert_eq!(err.position(), Some((2, 1)));
+    }
+}
@@ -0,0 +1,139 @@
+use std::{
+    env, fs,
+    io::{BufRead, Read, stdin},
+    process::ExitCode,
+};
+
+use report::{DayReport, Format};
+
+pub mod answers;
+pub mod dsu;
+pub mod grid;
+pub mod kv;
+pub mod parse;
+pub mod ranges;
+pub mod report;
+
+pub use answers::Answers;
+pub use dsu::DisjointSetUnion;
+pub use grid::Grid;
+pub use parse::ParseError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+/// A single day's puzzle: how to parse the input and how to solve both parts.
+///
+/// Parts that need extra knobs (e.g. how many joins to perform) take them
+/// through `Part1Params` / `Part2Params`, whose `Default` is the value the
+/// real puzzle asks for.
+pub trait Solution {
+    const DAY: u8;
+
+    type Input;
+    type Part1Params: Default;
+    type Part2Params: Default;
+
+    fn parse(input: impl BufRead) -> Result<Self::Input, ParseError>;
+    fn part_1(input: &Self::Input, params: &Self::Part1Params) -> u64;
+    fn part_2(input: &Self::Input, params: &Self::Part2Params) -> u64;
+
+    /// Parameters the puzzle text uses for its worked example, when they
+    /// differ from the real puzzle's.
+    fn part_1_example_params() -> Self::Part1Params {
+        Default::default()
+    }
+
+    fn part_2_example_params() -> Self::Part2Params {
+        Default::default()
+    }
+
+    /// Flags the day's binary and `aoc run` accept on top of the shared ones,
+    /// each switching on a non-default parameter.
+    const FLAGS: &'static [&'static str] = &[];
+
+    /// Applies one of `FLAGS` to the parameters.
+    fn apply_flag(_flag: &str, _part_1: &mut Self::Part1Params, _part_2: &mut Self::Part2Params) {}
+}
+
+/// Entry point for a day's binary: `dayN [--format text|json] [<flag>...]
+/// [<input>]`. Reads stdin when no input path is given and solves both parts
+/// with their default parameters, adjusted by any of the day's `FLAGS`.
+pub fn run<S: Solution>() -> ExitCode {
+    let mut format = Format::Text;
+    let mut path = None;
+    let (mut part_1_params, mut part_2_params) = Default::default();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let parsed = match arg.as_str() {
+            "--format" => args
+                .next()
+                .ok_or("--format needs a value".to_string())
+                .and_then(|value| value.parse())
+                .map(|value| format = value),
+            flag if S::FLAGS.contains(&flag) => {
+                S::apply_flag(flag, &mut part_1_params, &mut part_2_params);
+                Ok(())
+            }
+            _ if path.is_none() && !arg.starts_with("--") => {
+                path = Some(arg);
+                Ok(())
+            }
+            _ => Err(format!("unexpected argument: {arg}")),
+        };
+        if let Err(message) = parsed {
+            let flags: String = S::FLAGS.iter().map(|flag| format!(" [{flag}]")).collect();
+            eprintln!(
+                "error: {message}\n\nusage: day{} [--format text|json]{flags} [<input>]",
+                S::DAY
+            );
+            return ExitCode::from(2);
+        }
+    }
+    let report = DayReport::run(
+        S::DAY,
+        path.as_deref().unwrap_or("-"),
+        &Part::BOTH,
+        || match &path {
+            Some(path) => S::parse(fs::read(path)?.as_slice()),
+            None => S::parse(stdin().lock()),
+        },
+        |input, part| match part {
+            Part::One => S::part_1(input, &part_1_params),
+            Part::Two => S::part_2(input, &part_2_params),
+        },
+    );
+    report.print(format);
+    if report.failed() {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Reads the file at `path`, or stdin when there is none, for binaries with
+/// their own modes. Errors name the path, or `-` for stdin.
+pub fn read_input(path: Option<&str>) -> Result<Vec<u8>, String> {
+    match path {
+        Some(path) => fs::read(path),
+        None => {
+            let mut text = Vec::new();
+            stdin().read_to_end(&mut text).map(|_| text)
+        }
+    }
+    .map_err(|e| format!("{}: {e}", path.unwrap_or("-")))
+}
@@ -0,0 +1,171 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    io::{self, BufRead},
+    str::FromStr,
+};
+
+/// Why an input could not be parsed. Lines and columns are 1-based; an empty
+/// `found` means the line (or input) ended where more was expected.
+#[derive(Debug)]
+pub enum ParseError {
+    Io(io::Error),
+    Invalid {
+        line: usize,
+        column: usize,
+        found: String,
+        expected: String,
+    },
+}
+
+impl ParseError {
+    pub fn new(
+        line: usize,
+        column: usize,
+        found: impl Into<String>,
+        expected: impl Into<String>,
+    ) -> Self {
+        ParseError::Invalid {
+            line,
+            column,
+            found: found.into(),
+            expected: expected.into(),
+        }
+    }
+
+    /// The `(line, column)` the error points at, if it came from the text.
+    pub fn position(&self) -> Option<(usize, usize)> {
+        match self {
+            ParseError::Io(_) => None,
+            ParseError::Invalid { line, column, .. } => Some((*line, *column)),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Io(err) => write!(f, "could not read input: {err}"),
+            ParseError::Invalid {
+                line,
+                column,
+                found,
+                expected,
+            } => {
+                write!(f, "line {line}, column {column}: expected {expected}, ")?;
+                if found.is_empty() {
+                    write!(f, "found end of line")
+                } else {
+                    write!(f, "found \"{found}\"")
+                }
+            }
+        }
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseError::Io(err) => Some(err),
+            ParseError::Invalid { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for ParseError {
+    fn from(err: io::Error) -> Self {
+        ParseError::Io(err)
+    }
+}
+
+pub fn read_to_string(mut input: impl BufRead) -> Result<String, ParseError> {
+    let mut text = String::new();
+    input.read_to_string(&mut text)?;
+    Ok(text)
+}
+
+/// The lines of `text` paired with their 1-based line numbers.
+pub fn numbered_lines(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.lines().enumerate().map(|(i, line)| (i + 1, line))
+}
+
+/// Splits `line` on `separator`, pairing each field with its 1-based column.
+pub fn fields(line: &str, separator: char) -> impl Iterator<Item = (usize, &str)> {
+    let mut column = 1;
+    line.split(separator).map(move |field| {
+        let start = column;
+        column += field.chars().count() + 1;
+        (start, field)
+    })
+}
+
+/// Parses a single field found at `line`/`column`.
+pub fn field<T: FromStr>(
+    line: usize,
+    column: usize,
+    text: &str,
+    expected: &str,
+) -> Result<T, ParseError> {
+    text.parse()
+        .map_err(|_| ParseError::new(line, column, text, expected))
+}
+
+/// Takes the next `(column, text)` from a [`fields`] iterator and parses it,
+/// reporting the end of `whole_line` if there are no fields left.
+pub fn next_field<'a, T: FromStr>(
+    fields: &mut impl Iterator<Item = (usize, &'a str)>,
+    line: usize,
+    whole_line: &str,
+    expected: &str,
+) -> Result<T, ParseError> {
+    match fields.next() {
+        Some((column, text)) => field(line, column, text, expected),
+        None => Err(ParseError::new(
+            line,
+            whole_line.chars().count() + 1,
+            "",
+            expected,
+        )),
+    }
+}
+
+/// Fails if a [`fields`] iterator still has fields left.
+pub fn end_of_fields<'a>(
+    fields: &mut impl Iterator<Item = (usize, &'a str)>,
+    line: usize,
+) -> Result<(), ParseError> {
+    match fields.next() {
+        Some((column, text)) => Err(ParseError::new(line, column, text, "end of line")),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fields_columns() {
+        let fields: Vec<_> = fields("12,3,,456", ',').collect();
+        assert_eq!(fields, vec![(1, "12"), (4, "3"), (6, ""), (7, "456")]);
+    }
+
+    #[test]
+    fn test_next_field_reports_position() {
+        let line = "162,abc";
+        let mut split = fields(line, ',');
+        assert_eq!(next_field::<u64>(&mut split, 4, line, "x").unwrap(), 162);
+        let err = next_field::<u64>(&mut split, 4, line, "a coordinate").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 4, column 5: expected a coordinate, found \"abc\""
+        );
+        let err = next_field::<u64>(&mut split, 4, line, "a coordinate").unwrap_err();
+        assert_eq!(err.position(), Some((4, 8)));
+        assert!(end_of_fields(&mut split, 4).is_ok());
+        assert_eq!(
+            err.to_string(),
+            "line 4, column 8: expected a coordinate, found end of line"
+        );
+    }
+}
@@ -0,0 +1,45 @@
+//! Sets of IDs given as inclusive ranges.
+
+use std::ops::RangeInclusive;
+
+/// The same IDs as `ranges`, as sorted ranges that don't overlap. Empty
+/// ranges are dropped.
+pub fn merge(ranges: &[RangeInclusive<u64>]) -> Vec<RangeInclusive<u64>> {
+    let mut sorted: Vec<RangeInclusive<u64>> = ranges
+        .iter()
+        .filter(|range| !range.is_empty())
+        .cloned()
+        .collect();
+    sorted.sort_by_key(|range| *range.start());
+    let mut merged = Vec::<RangeInclusive<u64>>::new();
+    for range in sorted {
+        match merged.last_mut() {
+            Some(current) if current.end() >= range.start() => {
+                *current = *current.start()..=*range.end().max(current.end());
+            }
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge() {
+        assert_eq!(
+            merge(&[
+                10..=14,
+                3..=5,
+                16..=20,
+                12..=18,
+                RangeInclusive::new(9, 8),
+                5..=5
+            ]),
+            vec![3..=5, 10..=20]
+        );
+        assert_eq!(merge(&[]), vec![]);
+    }
+}
@@ -0,0 +1,303 @@
+//! Results of running a day, printed either as the familiar `part 1: ...`
+//! text or as JSON Lines for tooling.
+//!
+//! The JSON form is one object per requested part:
+//!
+//! ```json
+//! {"day":8,"part":1,"input":"days/day8/input.txt","success":true,"answer":"131580","parse_duration_ns":171210,"duration_ns":151010000,"error":null}
+//! ```
+//!
+//! `answer` is a string so 64-bit answers survive JSON parsers that use
+//! doubles. When parsing fails every requested part is reported with
+//! `success: false`, a null `answer` and `duration_ns`, and the parse error;
+//! `parse_duration_ns` is null then too. A part that panics is reported the
+//! same way, with the panic message as its error, and the other parts still
+//! run.
+
+use std::{
+    any::Any,
+    fmt::Write,
+    panic::{self, AssertUnwindSafe},
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use crate::{ParseError, Part};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("invalid format: {s} (expected text or json)")),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct DayReport {
+    pub day: u8,
+    /// Where the input came from, e.g. a path or `-` for stdin.
+    pub input: String,
+    pub parse_duration: Option<Duration>,
+    pub parts: Vec<PartReport>,
+}
+
+#[derive(Debug)]
+pub struct PartReport {
+    pub part: Part,
+    pub result: Result<Answer, String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Answer {
+    pub value: u64,
+    pub duration: Duration,
+}
+
+impl DayReport {
+    /// Times `parse`, then `solve` for each of `parts`. Panics in either are
+    /// caught and reported as errors.
+    pub fn run<T>(
+        day: u8,
+        input: impl Into<String>,
+        parts: &[Part],
+        parse: impl FnOnce() -> Result<T, ParseError>,
+        solve: impl Fn(&T, Part) -> u64,
+    ) -> DayReport {
+        let start = Instant::now();
+        let parsed = match panic::catch_unwind(AssertUnwindSafe(parse)) {
+            Ok(parsed) => parsed.map_err(|err| err.to_string()),
+            Err(payload) => Err(format!("parsing panicked: {}", panic_message(payload))),
+        };
+        let parse_duration = start.elapsed();
+        let (parse_duration, parts) = match parsed {
+            Ok(parsed) => {
+                let parts = parts
+                    .iter()
+                    .map(|&part| {
+                        let start = Instant::now();
+                        let solved = panic::catch_unwind(AssertUnwindSafe(|| solve(&parsed, part)));
+                        let duration = start.elapsed();
+                        let result = match solved {
+                            Ok(value) => Ok(Answer { value, duration }),
+                            Err(payload) => Err(format!(
+                                "part {} panicked: {}",
+                                part.number(),
+                                panic_message(payload)
+                            )),
+                        };
+                        PartReport { part, result }
+                    })
+                    .collect();
+                (Some(parse_duration), parts)
+            }
+            Err(message) => {
+                let parts = parts
+                    .iter()
+                    .map(|&part| PartReport {
+                        part,
+                        result: Err(message.clone()),
+                    })
+                    .collect();
+                (None, parts)
+            }
+        };
+        DayReport {
+            day,
+            input: input.into(),
+            parse_duration,
+            parts,
+        }
+    }
+
+    pub fn failed(&self) -> bool {
+        self.parts.iter().any(|part| part.result.is_err())
+    }
+
+    /// Prints to stdout, except that in text mode errors go to stderr, each
+    /// distinct one once.
+    pub fn print(&self, format: Format) {
+        match format {
+            Format::Json => {
+                for line in self.json_lines() {
+                    println!("{line}");
+                }
+            }
+            Format::Text => {
+                let mut last_error = None;
+                for part in self.parts.iter() {
+                    match &part.result {
+                        Ok(answer) => println!("part {}: {}", part.part.number(), answer.value),
+                        // A parse error is repeated for every part; say it once.
+                        Err(message) if last_error != Some(message) => {
+                            eprintln!("error: {}: {message}", self.input);
+                            last_error = Some(message);
+                        }
+                        Err(_) => {}
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn json_lines(&self) -> Vec<String> {
+        let nanos = |duration: Duration| duration.as_nanos().to_string();
+        self.parts
+            .iter()
+            .map(|part| {
+                let (answer, duration, error) = match &part.result {
+                    Ok(answer) => (
+                        json_string(&answer.value.to_string()),
+                        nanos(answer.duration),
+                        None,
+                    ),
+                    Err(message) => ("null".to_string(), "null".to_string(), Some(message)),
+                };
+                let mut line = String::new();
+                write!(
+                    line,
+                    "{{\"day\":{},\"part\":{},\"input\":{},\"success\":{},\"answer\":{answer},\
+                     \"parse_duration_ns\":{},\"duration_ns\":{duration},\"error\":{}}}",
+                    self.day,
+                    part.part.number(),
+                    json_string(&self.input),
+                    error.is_none(),
+                    self.parse_duration.map_or("null".to_string(), nanos),
+                    error.map_or("null".to_string(), |message| json_string(message)),
+                )
+                .expect("writing to a String cannot fail");
+                line
+            })
+            .collect()
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                write!(out, "\\u{:04x}", c as u32).expect("writing to a String cannot fail")
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_lines_success() {
+        let report = DayReport {
+            day: 8,
+            input: "days/day8/input.txt".to_string(),
+            parse_duration: Some(Duration::from_nanos(1500)),
+            parts: vec![PartReport {
+                part: Part::Two,
+                result: Ok(Answer {
+                    value: 6844224,
+                    duration: Duration::from_micros(2),
+                }),
+            }],
+        };
+        assert_eq!(
+            report.json_lines(),
+            vec![
+                "{\"day\":8,\"part\":2,\"input\":\"days/day8/input.txt\",\"success\":true,\
+                 \"answer\":\"6844224\",\"parse_duration_ns\":1500,\"duration_ns\":2000,\"error\":null}"
+            ]
+        );
+        assert!(!report.failed());
+    }
+
+    #[test]
+    fn test_run_reports_parse_error_for_each_part() {
+        let report = DayReport::run(
+            1,
+            "-",
+            &Part::BOTH,
+            || Err::<(), _>(ParseError::new(3, 1, "X", "`L` or `R`")),
+            |_, _| unreachable!(),
+        );
+        assert!(report.failed());
+        let lines = report.json_lines();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[1],
+            "{\"day\":1,\"part\":2,\"input\":\"-\",\"success\":false,\"answer\":null,\
+             \"parse_duration_ns\":null,\"duration_ns\":null,\
+             \"error\":\"line 3, column 1: expected `L` or `R`, found \\\"X\\\"\"}"
+        );
+    }
+
+    #[test]
+    fn test_run_reports_panicking_part_and_keeps_going() {
+        let report = DayReport::run(
+            6,
+            "-",
+            &Part::BOTH,
+            || Ok(3),
+            |&input, part| match part {
+                Part::One => panic!("no answer for {input}"),
+                Part::Two => input * 2,
+            },
+        );
+        assert!(report.failed());
+        assert_eq!(
+            report.parts[0].result,
+            Err("part 1 panicked: no answer for 3".to_string())
+        );
+        assert_eq!(
+            report.parts[1].result.as_ref().map(|answer| answer.value),
+            Ok(6)
+        );
+        assert!(report.json_lines()[0].contains("\"success\":false,\"answer\":null"));
+
+        let report = DayReport::run(
+            6,
+            "-",
+            &[Part::Two],
+            || -> Result<u64, _> { panic!("bad") },
+            |_, _| 0,
+        );
+        assert_eq!(
+            report.parts[0].result,
+            Err("parsing panicked: bad".to_string())
+        );
+    }
+
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!("json".parse(), Ok(Format::Json));
+        assert!("yaml".parse::<Format>().is_err());
+    }
+}
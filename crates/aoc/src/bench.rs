@@ -0,0 +1,106 @@
+use std::{
+    fmt::{self, Display},
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+/// How long to spend timing a phase when no run count is given.
+const TIME_BUDGET: Duration = Duration::from_secs(1);
+const MIN_RUNS: usize = 5;
+const MAX_RUNS: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Stats {
+        assert!(!samples.is_empty(), "no samples to summarise");
+        samples.sort();
+        let percentile = |p: usize| samples[(samples.len() - 1) * p / 100];
+        Stats {
+            runs: samples.len(),
+            min: samples[0],
+            median: percentile(50),
+            p95: percentile(95),
+        }
+    }
+
+    /// Runs per second, based on the median.
+    pub fn per_second(&self) -> f64 {
+        1.0 / self.median.as_secs_f64().max(f64::MIN_POSITIVE)
+    }
+}
+
+/// Times `f` once to size the run count, then `runs` more times (or as many
+/// as fit the time budget).
+pub fn measure<T>(runs: Option<usize>, mut f: impl FnMut() -> T) -> Stats {
+    let start = Instant::now();
+    black_box(f());
+    let first = start.elapsed();
+    let runs = runs.unwrap_or_else(|| {
+        let fit = TIME_BUDGET.as_nanos() / first.as_nanos().max(1);
+        (fit as usize).clamp(MIN_RUNS, MAX_RUNS)
+    });
+    let samples = (0..runs)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect();
+    Stats::from_samples(samples)
+}
+
+pub struct Human(pub Duration);
+
+impl Display for Human {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nanos = self.0.as_nanos() as f64;
+        let (value, unit) = if nanos < 1e3 {
+            (nanos, "ns")
+        } else if nanos < 1e6 {
+            (nanos / 1e3, "µs")
+        } else if nanos < 1e9 {
+            (nanos / 1e6, "ms")
+        } else {
+            (nanos / 1e9, "s")
+        };
+        let text = format!("{value:.2}{unit}");
+        f.pad(&text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_from_samples() {
+        let samples = (1..=100).rev().map(Duration::from_millis).collect();
+        let stats = Stats::from_samples(samples);
+        assert_eq!(stats.runs, 100);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(50));
+        assert_eq!(stats.p95, Duration::from_millis(95));
+    }
+
+    #[test]
+    fn test_measure_fixed_runs() {
+        let mut calls = 0;
+        let stats = measure(Some(7), || calls += 1);
+        assert_eq!(stats.runs, 7);
+        assert_eq!(calls, 8);
+    }
+
+    #[test]
+    fn test_human_duration() {
+        assert_eq!(Human(Duration::from_nanos(512)).to_string(), "512.00ns");
+        assert_eq!(Human(Duration::from_micros(1500)).to_string(), "1.50ms");
+        assert_eq!(format!("{:>8}", Human(Duration::from_secs(2))), "   2.00s");
+    }
+}
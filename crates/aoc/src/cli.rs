@@ -0,0 +1,342 @@
+use std::path::PathBuf;
+
+use aoc_common::{Part, report::Format};
+
+use crate::registry::Day;
+
+pub const USAGE: &str = "\
+usage:
+    aoc run <day> [--part 1|2] [--input input|example|<path>] [--format text|json] [<day flag>...]
+    aoc run --all [--part 1|2] [--input input|example] [--format text|json]
+    aoc verify [<day>|--all]
+    aoc bench <day>|--all [--part 1|2] [--input ...] [--runs <n>]
+    aoc new <day>
+    aoc fetch <day>
+    aoc extract <day> <page.html> [--block <n>]";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Run(RunArgs),
+    Verify(DaySelection),
+    Bench(BenchArgs),
+    New(u8),
+    Fetch(u8),
+    Extract(ExtractArgs),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ExtractArgs {
+    pub day: u8,
+    pub page: PathBuf,
+    /// Which `<pre><code>` block on the page is the example, counting from 1.
+    pub block: usize,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct RunArgs {
+    pub days: DaySelection,
+    pub parts: Vec<Part>,
+    pub input: InputSource,
+    pub format: Format,
+    /// Day-specific flags such as day 2's `--per-range`, checked against the
+    /// day once it's looked up.
+    pub flags: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct BenchArgs {
+    pub run: RunArgs,
+    /// Fixed number of timed runs per phase; otherwise runs are sized to a
+    /// time budget.
+    pub runs: Option<usize>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DaySelection {
+    All,
+    One(u8),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Input,
+    Example,
+    Path(PathBuf),
+}
+
+impl InputSource {
+    pub fn resolve(&self, day: &Day) -> PathBuf {
+        match self {
+            InputSource::Input => day.dir().join("input.txt"),
+            InputSource::Example => day.dir().join("example.txt"),
+            InputSource::Path(path) => path.clone(),
+        }
+    }
+}
+
+pub fn parse(args: &[String]) -> Result<Command, String> {
+    let (command, rest) = args.split_first().ok_or("missing command")?;
+    match command.as_str() {
+        "run" => parse_run(rest).map(Command::Run),
+        "verify" => parse_verify(rest).map(Command::Verify),
+        "bench" => parse_bench(rest).map(Command::Bench),
+        "new" => match rest {
+            [day] => parse_day(day).map(Command::New),
+            _ => Err("new takes exactly one day".to_string()),
+        },
+        "extract" => parse_extract(rest).map(Command::Extract),
+        "fetch" => match rest {
+            [day] => parse_day(day).map(Command::Fetch),
+            _ => Err("fetch takes exactly one day".to_string()),
+        },
+        _ => Err(format!("unknown command: {command}")),
+    }
+}
+
+fn parse_run(args: &[String]) -> Result<RunArgs, String> {
+    let mut days = None;
+    let mut parts = Part::BOTH.to_vec();
+    let mut input = InputSource::Input;
+    let mut format = Format::Text;
+    let mut flags = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--all" => days = Some(DaySelection::All),
+            "--part" => {
+                let value = args.next().ok_or("--part needs a value")?;
+                parts = match value.as_str() {
+                    "1" => vec![Part::One],
+                    "2" => vec![Part::Two],
+                    _ => return Err(format!("invalid part: {value}")),
+                };
+            }
+            "--input" => {
+                let value = args.next().ok_or("--input needs a value")?;
+                input = match value.as_str() {
+                    "input" => InputSource::Input,
+                    "example" => InputSource::Example,
+                    path => InputSource::Path(PathBuf::from(path)),
+                };
+            }
+            "--format" => {
+                format = args.next().ok_or("--format needs a value")?.parse()?;
+            }
+            flag if flag.starts_with("--") => flags.push(flag.to_string()),
+            day => days = Some(DaySelection::One(parse_day(day)?)),
+        }
+    }
+    let days = days.ok_or("expected a day or --all")?;
+    if days == DaySelection::All && matches!(input, InputSource::Path(_)) {
+        return Err("--input <path> needs a single day".to_string());
+    }
+    if days == DaySelection::All && !flags.is_empty() {
+        return Err(format!("{} needs a single day", flags[0]));
+    }
+    Ok(RunArgs {
+        days,
+        parts,
+        input,
+        format,
+        flags,
+    })
+}
+
+fn parse_verify(args: &[String]) -> Result<DaySelection, String> {
+    match args {
+        [] => Ok(DaySelection::All),
+        [all] if all == "--all" => Ok(DaySelection::All),
+        [day] => parse_day(day).map(DaySelection::One),
+        _ => Err("verify takes at most one day".to_string()),
+    }
+}
+
+fn parse_bench(args: &[String]) -> Result<BenchArgs, String> {
+    let mut runs = None;
+    let mut rest = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--runs" {
+            let value = args.next().ok_or("--runs needs a value")?;
+            runs = match value.parse() {
+                Ok(0) | Err(_) => return Err(format!("invalid run count: {value}")),
+                Ok(runs) => Some(runs),
+            };
+        } else {
+            rest.push(arg.clone());
+        }
+    }
+    let run = parse_run(&rest)?;
+    if run.format != Format::Text {
+        return Err("bench only prints text".to_string());
+    }
+    Ok(BenchArgs { run, runs })
+}
+
+fn parse_extract(args: &[String]) -> Result<ExtractArgs, String> {
+    let mut positional = Vec::new();
+    let mut block = 1;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--block" {
+            let value = args.next().ok_or("--block needs a value")?;
+            block = match value.parse() {
+                Ok(0) | Err(_) => return Err(format!("invalid block: {value}")),
+                Ok(block) => block,
+            };
+        } else {
+            positional.push(arg);
+        }
+    }
+    match positional.as_slice() {
+        [day, page] => Ok(ExtractArgs {
+            day: parse_day(day)?,
+            page: PathBuf::from(page),
+            block,
+        }),
+        _ => Err("extract takes a day and a saved puzzle page".to_string()),
+    }
+}
+
+fn parse_day(day: &str) -> Result<u8, String> {
+    day.parse().map_err(|_| format!("invalid day: {day}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_str(args: &str) -> Result<Command, String> {
+        let args: Vec<String> = args.split_whitespace().map(String::from).collect();
+        parse(&args)
+    }
+
+    #[test]
+    fn test_run_single_day() {
+        assert_eq!(
+            parse_str("run 7"),
+            Ok(Command::Run(RunArgs {
+                days: DaySelection::One(7),
+                parts: vec![Part::One, Part::Two],
+                input: InputSource::Input,
+                format: Format::Text,
+                flags: vec![],
+            }))
+        );
+    }
+
+    #[test]
+    fn test_run_part_and_input() {
+        assert_eq!(
+            parse_str("run 8 --part 2 --input example"),
+            Ok(Command::Run(RunArgs {
+                days: DaySelection::One(8),
+                parts: vec![Part::Two],
+                input: InputSource::Example,
+                format: Format::Text,
+                flags: vec![],
+            }))
+        );
+    }
+
+    #[test]
+    fn test_run_all() {
+        assert_eq!(
+            parse_str("run --all --part 1"),
+            Ok(Command::Run(RunArgs {
+                days: DaySelection::All,
+                parts: vec![Part::One],
+                input: InputSource::Input,
+                format: Format::Text,
+                flags: vec![],
+            }))
+        );
+    }
+
+    #[test]
+    fn test_run_json() {
+        assert_eq!(
+            parse_str("run --all --format json"),
+            Ok(Command::Run(RunArgs {
+                days: DaySelection::All,
+                parts: vec![Part::One, Part::Two],
+                input: InputSource::Input,
+                format: Format::Json,
+                flags: vec![],
+            }))
+        );
+        assert!(parse_str("run 1 --format yaml").is_err());
+        assert!(parse_str("bench 1 --format json").is_err());
+    }
+
+    #[test]
+    fn test_run_day_flags() {
+        assert_eq!(
+            parse_str("run 2 --per-range --input example"),
+            Ok(Command::Run(RunArgs {
+                days: DaySelection::One(2),
+                parts: vec![Part::One, Part::Two],
+                input: InputSource::Example,
+                format: Format::Text,
+                flags: vec!["--per-range".to_string()],
+            }))
+        );
+        assert!(parse_str("run --all --per-range").is_err());
+    }
+
+    #[test]
+    fn test_verify() {
+        assert_eq!(parse_str("verify"), Ok(Command::Verify(DaySelection::All)));
+        assert_eq!(
+            parse_str("verify --all"),
+            Ok(Command::Verify(DaySelection::All))
+        );
+        assert_eq!(
+            parse_str("verify 4"),
+            Ok(Command::Verify(DaySelection::One(4)))
+        );
+        assert!(parse_str("verify 4 5").is_err());
+    }
+
+    #[test]
+    fn test_bench() {
+        assert_eq!(
+            parse_str("bench 2 --runs 50 --part 2"),
+            Ok(Command::Bench(BenchArgs {
+                run: RunArgs {
+                    days: DaySelection::One(2),
+                    parts: vec![Part::Two],
+                    input: InputSource::Input,
+                    format: Format::Text,
+                    flags: vec![],
+                },
+                runs: Some(50),
+            }))
+        );
+        assert!(parse_str("bench 2 --runs 0").is_err());
+    }
+
+    #[test]
+    fn test_extract() {
+        assert_eq!(
+            parse_str("extract 3 page.html --block 2"),
+            Ok(Command::Extract(ExtractArgs {
+                day: 3,
+                page: PathBuf::from("page.html"),
+                block: 2,
+            }))
+        );
+        assert!(parse_str("extract 3").is_err());
+    }
+
+    #[test]
+    fn test_run_rejects_bad_arguments() {
+        assert!(parse_str("run").is_err());
+        assert!(parse_str("run x").is_err());
+        assert!(parse_str("run 3 --part 3").is_err());
+        assert!(parse_str("run --all --input some/file.txt").is_err());
+        assert!(parse_str("jump 3").is_err());
+        assert!(parse_str("new").is_err());
+        assert!(parse_str("fetch 1 2").is_err());
+    }
+}
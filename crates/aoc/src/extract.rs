@@ -0,0 +1,161 @@
+use std::{fs, path::Path};
+
+use aoc_common::Answers;
+
+/// What a saved puzzle page says about its worked example.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Extracted {
+    /// Every `<pre><code>` block on the page, in order.
+    pub blocks: Vec<String>,
+    /// The last emphasized number in each part's description.
+    pub part_1: Option<u64>,
+    pub part_2: Option<u64>,
+}
+
+/// Pulls the example blocks and expected answers out of a puzzle page.
+///
+/// Each part is an `<article class="day-desc">`; its expected example answer
+/// is the last `<code><em>N</em></code>` in it, which is how the puzzle text
+/// highlights the result of the worked example.
+pub fn extract(html: &str) -> Extracted {
+    let articles = sections(html, "<article", "</article>");
+    let answer = |index: usize| {
+        articles
+            .get(index)
+            .and_then(|(_, article)| last_answer(article))
+    };
+    Extracted {
+        blocks: sections(html, "<pre><code>", "</code></pre>")
+            .into_iter()
+            .map(|(_, block)| decode_entities(&strip_tags(block)))
+            .collect(),
+        part_1: answer(0),
+        part_2: answer(1),
+    }
+}
+
+/// Writes the chosen example block to `example.txt` and the answers to
+/// `example_answers.toml` in `dir`. An answer the page doesn't have yet
+/// (part 2 before part 1 is solved) keeps its previous value.
+pub fn write_example(dir: &Path, extracted: &Extracted, block: usize) -> Result<(), String> {
+    let example = extracted.blocks.get(block).ok_or(format!(
+        "the page has {} example block(s), no block {block}",
+        extracted.blocks.len()
+    ))?;
+    let answers_path = dir.join("example_answers.toml");
+    let previous = Answers::load(&answers_path)?;
+    let mut answers = String::new();
+    for (name, answer) in [
+        ("part_1", extracted.part_1.or(previous.part_1)),
+        ("part_2", extracted.part_2.or(previous.part_2)),
+    ] {
+        if let Some(answer) = answer {
+            answers.push_str(&format!("{name} = {answer}\n"));
+        }
+    }
+    for (path, contents) in [(dir.join("example.txt"), example), (answers_path, &answers)] {
+        fs::write(&path, contents).map_err(|e| format!("{}: {e}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// The text between each `open` (which may carry attributes, e.g.
+/// `<article class=...>`) and the following `close`, with its byte offset.
+fn sections<'a>(html: &'a str, open: &str, close: &str) -> Vec<(usize, &'a str)> {
+    let mut sections = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find(open) {
+        let after_open = &rest[start + open.len()..];
+        let body_start = if open.ends_with('>') {
+            0
+        } else {
+            match after_open.find('>') {
+                Some(i) => i + 1,
+                None => break,
+            }
+        };
+        let body = &after_open[body_start..];
+        let Some(end) = body.find(close) else {
+            break;
+        };
+        sections.push((html.len() - body.len(), &body[..end]));
+        rest = &body[end + close.len()..];
+    }
+    sections
+}
+
+fn last_answer(article: &str) -> Option<u64> {
+    let mut candidates = sections(article, "<code><em>", "</em></code>");
+    candidates.extend(sections(article, "<em><code>", "</code></em>"));
+    candidates.sort_by_key(|(offset, _)| *offset);
+    candidates
+        .into_iter()
+        .rev()
+        .find_map(|(_, text)| decode_entities(&strip_tags(text)).trim().parse().ok())
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 3: Lobby ---</h2>
+<p>For example:</p>
+<pre><code>987654321111111
+811111111111119
+</code></pre>
+<p>In <code>987654321111111</code>, you can make <code><em>98</em></code>.</p>
+<p>The total output joltage is <code><em>98</em></code> + ... = <code><em>357</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>17332</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<pre><code>a &lt;b&gt; <em>c</em> &amp; d
+</code></pre>
+<p>Now the total is <em><code>3121910778619</code></em>.</p>
+</article>
+</main></body></html>"#;
+
+    #[test]
+    fn test_extract_blocks_and_answers() {
+        let extracted = extract(PAGE);
+        assert_eq!(
+            extracted.blocks,
+            vec![
+                "987654321111111\n811111111111119\n".to_string(),
+                "a <b> c & d\n".to_string(),
+            ]
+        );
+        assert_eq!(extracted.part_1, Some(357));
+        assert_eq!(extracted.part_2, Some(3121910778619));
+    }
+
+    #[test]
+    fn test_extract_part_one_only() {
+        let part_one = &PAGE[..PAGE.find("<p>Your puzzle").unwrap()];
+        let extracted = extract(part_one);
+        assert_eq!(extracted.part_1, Some(357));
+        assert_eq!(extracted.part_2, None);
+    }
+}
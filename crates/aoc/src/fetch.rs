@@ -0,0 +1,235 @@
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+use aoc_common::{ParseError, kv};
+
+pub const CONFIG_FILE: &str = "aoc.toml";
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const DEFAULT_YEAR: u16 = 2025;
+const USER_AGENT: &str = concat!(
+    "github.com/DeanWay/advent-of-code-2025 aoc/",
+    env!("CARGO_PKG_VERSION")
+);
+
+/// Settings for talking to the puzzle site, read from `aoc.toml` in the
+/// workspace root:
+///
+/// ```toml
+/// session = "53616c74..."
+/// base_url = "https://adventofcode.com"
+/// year = 2025
+/// ```
+///
+/// `AOC_SESSION` and `AOC_BASE_URL` override the file.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Config {
+    pub session: Option<String>,
+    pub base_url: String,
+    pub year: u16,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            session: None,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            year: DEFAULT_YEAR,
+        }
+    }
+}
+
+impl Config {
+    pub fn load(root: &Path) -> Result<Config, String> {
+        let path = root.join(CONFIG_FILE);
+        let mut config = match fs::read_to_string(&path) {
+            Ok(text) => Config::parse(&text).map_err(|e| format!("{}: {e}", path.display()))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Config::default(),
+            Err(e) => return Err(format!("{}: {e}", path.display())),
+        };
+        if let Ok(session) = env::var("AOC_SESSION") {
+            config.session = Some(session);
+        }
+        if let Ok(base_url) = env::var("AOC_BASE_URL") {
+            config.base_url = base_url;
+        }
+        Ok(config)
+    }
+
+    pub fn parse(text: &str) -> Result<Config, ParseError> {
+        let mut config = Config::default();
+        for entry in kv::entries(text)? {
+            match entry.key {
+                "session" => config.session = Some(entry.string()?),
+                "base_url" => config.base_url = entry.string()?,
+                "year" => config.year = entry.number("a year")?,
+                _ => return Err(entry.unknown_key("`session`, `base_url` or `year`")),
+            }
+        }
+        Ok(config)
+    }
+
+    fn input_url(&self, day: u8) -> String {
+        let base_url = self.base_url.trim_end_matches('/');
+        format!("{base_url}/{}/day/{day}/input", self.year)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Fetched {
+    /// The input was already on disk; nothing was requested.
+    Cached,
+    Downloaded {
+        bytes: usize,
+    },
+}
+
+/// Downloads `day`'s input to `path` unless a non-empty file is already
+/// there. Freshly scaffolded days have an empty `input.txt`, which counts as
+/// missing.
+pub fn fetch_input(config: &Config, day: u8, path: &Path) -> Result<Fetched, String> {
+    if fs::metadata(path).is_ok_and(|meta| meta.len() > 0) {
+        return Ok(Fetched::Cached);
+    }
+    let session = config.session.as_deref().ok_or(format!(
+        "no session token: set AOC_SESSION or `session` in {CONFIG_FILE}"
+    ))?;
+    let url = config.input_url(day);
+    let response = ureq::get(&url)
+        .set("User-Agent", USER_AGENT)
+        .set("Cookie", &format!("session={session}"))
+        .call()
+        .map_err(|e| match e {
+            ureq::Error::Status(code, _) => {
+                format!("{url}: server returned {code}; check the session token and day")
+            }
+            ureq::Error::Transport(transport) => format!("{url}: {transport}"),
+        })?;
+    let body = response.into_string().map_err(|e| format!("{url}: {e}"))?;
+    let partial = partial_path(path);
+    fs::write(&partial, &body).map_err(|e| format!("{}: {e}", partial.display()))?;
+    fs::rename(&partial, path).map_err(|e| format!("{}: {e}", path.display()))?;
+    Ok(Fetched::Downloaded { bytes: body.len() })
+}
+
+fn partial_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".partial");
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    use super::*;
+
+    /// Serves a single request with `status` and `body`, handing back the
+    /// request head it received.
+    fn stub_server(
+        status: &'static str,
+        body: &'static str,
+    ) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut head = String::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim_end().is_empty() {
+                    break;
+                }
+                head.push_str(&line);
+            }
+            let mut stream = reader.into_inner();
+            write!(
+                stream,
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            head
+        });
+        (base_url, handle)
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-fetch-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_parse_config() {
+        let config =
+            Config::parse("session = \"abc\"\nbase_url = \"http://x\"\nyear = 2024").unwrap();
+        assert_eq!(
+            config,
+            Config {
+                session: Some("abc".to_string()),
+                base_url: "http://x".to_string(),
+                year: 2024,
+            }
+        );
+        assert!(Config::parse("token = \"abc\"").is_err());
+    }
+
+    #[test]
+    fn test_fetch_downloads_then_uses_cache() {
+        let (base_url, server) = stub_server("200 OK", "1,2\n3,4\n");
+        let config = Config {
+            session: Some("cafe".to_string()),
+            base_url: format!("{base_url}/"),
+            year: 2025,
+        };
+        let dir = temp_dir("download");
+        let path = dir.join("input.txt");
+        fs::write(&path, "").unwrap();
+
+        let fetched = fetch_input(&config, 7, &path).unwrap();
+        assert_eq!(fetched, Fetched::Downloaded { bytes: 8 });
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1,2\n3,4\n");
+        let head = server.join().unwrap();
+        assert!(head.starts_with("GET /2025/day/7/input HTTP/1.1\r\n"));
+        assert!(head.contains("Cookie: session=cafe\r\n"));
+        assert!(head.contains(&format!("User-Agent: {USER_AGENT}\r\n")));
+
+        // The stub only answers once, so a second request would fail.
+        assert_eq!(fetch_input(&config, 7, &path).unwrap(), Fetched::Cached);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_error_leaves_no_file() {
+        let (base_url, server) = stub_server("400 Bad Request", "Puzzle inputs differ by user.");
+        let config = Config {
+            session: Some("stale".to_string()),
+            base_url,
+            year: 2025,
+        };
+        let dir = temp_dir("error");
+        let path = dir.join("input.txt");
+        let err = fetch_input(&config, 3, &path).unwrap_err();
+        assert!(err.contains("returned 400"), "{err}");
+        assert!(!path.exists());
+        server.join().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_needs_session() {
+        let dir = temp_dir("session");
+        let err = fetch_input(&Config::default(), 1, &dir.join("input.txt")).unwrap_err();
+        assert!(err.contains("AOC_SESSION"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,229 @@
+use std::{env, fs, process::ExitCode};
+
+use aoc_common::{
+    Answers, Part,
+    report::{DayReport, Format},
+};
+use bench::{Human, Stats};
+use cli::{BenchArgs, Command, DaySelection, ExtractArgs, InputSource, RunArgs};
+use registry::{DAYS, Day};
+
+mod bench;
+mod cli;
+mod extract;
+mod fetch;
+mod registry;
+mod scaffold;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let command = match cli::parse(&args) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("error: {message}\n\n{}", cli::USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    let result = match command {
+        Command::Run(args) => run(&args),
+        Command::Verify(days) => verify(&days),
+        Command::Bench(args) => bench(&args),
+        Command::Fetch(day) => fetch(day),
+        Command::Extract(args) => extract(&args),
+        Command::New(day) => scaffold::new_day(registry::workspace_root(), day).map(|()| {
+            println!("created days/day{day} and registered it with the runner");
+        }),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("error: {message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn fetch(day: u8) -> Result<(), String> {
+    let root = registry::workspace_root();
+    let dir = root.join("days").join(format!("day{day}"));
+    if !dir.is_dir() {
+        return Err(format!(
+            "{} does not exist; create it with `aoc new {day}`",
+            dir.display()
+        ));
+    }
+    let path = dir.join("input.txt");
+    let config = fetch::Config::load(root)?;
+    match fetch::fetch_input(&config, day, &path)? {
+        fetch::Fetched::Cached => println!("{} already exists, not downloading", path.display()),
+        fetch::Fetched::Downloaded { bytes } => {
+            println!("downloaded {bytes} bytes to {}", path.display())
+        }
+    }
+    Ok(())
+}
+
+fn extract(args: &ExtractArgs) -> Result<(), String> {
+    let dir = registry::workspace_root()
+        .join("days")
+        .join(format!("day{}", args.day));
+    if !dir.is_dir() {
+        return Err(format!("{} does not exist", dir.display()));
+    }
+    let html =
+        fs::read_to_string(&args.page).map_err(|e| format!("{}: {e}", args.page.display()))?;
+    let extracted = extract::extract(&html);
+    extract::write_example(&dir, &extracted, args.block - 1)?;
+    let show = |answer: Option<u64>| answer.map_or("not found".to_string(), |a| a.to_string());
+    println!(
+        "wrote example block {} to {}",
+        args.block,
+        dir.join("example.txt").display()
+    );
+    println!("part 1 example answer: {}", show(extracted.part_1));
+    println!("part 2 example answer: {}", show(extracted.part_2));
+    Ok(())
+}
+
+fn select_days(selection: &DaySelection) -> Result<Vec<&'static Day>, String> {
+    match selection {
+        DaySelection::All => Ok(DAYS.iter().collect()),
+        DaySelection::One(number) => registry::find(*number)
+            .map(|day| vec![day])
+            .ok_or(format!("day {number} is not implemented")),
+    }
+}
+
+/// The days `aoc run` or `aoc bench` should cover, as long as each takes the
+/// day-specific flags given.
+fn select_run_days(args: &RunArgs) -> Result<Vec<&'static Day>, String> {
+    let days = select_days(&args.days)?;
+    for day in days.iter() {
+        if let Some(flag) = args
+            .flags
+            .iter()
+            .find(|flag| !day.flags.contains(&flag.as_str()))
+        {
+            return Err(format!("day {} does not take {flag}", day.number));
+        }
+    }
+    Ok(days)
+}
+
+/// Runs every selected day, reporting a day that fails to load or panics and
+/// moving on to the next one.
+fn run(args: &RunArgs) -> Result<(), String> {
+    let mut failed = 0;
+    let example = args.input == InputSource::Example;
+    for day in select_run_days(args)? {
+        if args.format == Format::Text {
+            println!("day {}", day.number);
+        }
+        let path = args.input.resolve(day);
+        let report = DayReport::run(
+            day.number,
+            path.display().to_string(),
+            &args.parts,
+            || (day.parse)(&fs::read(&path)?, example, &args.flags),
+            |input, part| input.solve(part),
+        );
+        report.print(args.format);
+        if report.failed() {
+            failed += 1;
+        }
+    }
+    if failed > 0 {
+        return Err(format!("{failed} day(s) failed"));
+    }
+    Ok(())
+}
+
+/// Checks each selected day's `input.txt` answers against its `answers.toml`.
+/// Parts without a recorded answer are reported as UNKNOWN and don't fail;
+/// a day whose answers or input can't be loaded, or a part that panics, is
+/// reported as an ERROR and the remaining days still run.
+fn verify(selection: &DaySelection) -> Result<(), String> {
+    let (mut passed, mut failed, mut unknown) = (0, 0, 0);
+    for day in select_days(selection)? {
+        let answers = match Answers::load(&day.dir().join("answers.toml")) {
+            Ok(answers) => answers,
+            Err(message) => {
+                println!("day {}: ERROR {message}", day.number);
+                failed += Part::BOTH.len();
+                continue;
+            }
+        };
+        let path = InputSource::Input.resolve(day);
+        let report = DayReport::run(
+            day.number,
+            path.display().to_string(),
+            &Part::BOTH,
+            || (day.parse)(&fs::read(&path)?, false, &[]),
+            |input, part| input.solve(part),
+        );
+        for part in report.parts.iter() {
+            let status = match (&part.result, answers.get(part.part)) {
+                (Err(message), _) => {
+                    failed += 1;
+                    format!("ERROR {}: {message}", report.input)
+                }
+                (Ok(answer), None) => {
+                    unknown += 1;
+                    format!("UNKNOWN {}", answer.value)
+                }
+                (Ok(answer), Some(expected)) if expected == answer.value => {
+                    passed += 1;
+                    format!("PASS {expected}")
+                }
+                (Ok(answer), Some(expected)) => {
+                    failed += 1;
+                    format!("FAIL {} (expected {expected})", answer.value)
+                }
+            };
+            println!("day {} part {}: {status}", day.number, part.part.number());
+        }
+    }
+    println!("{passed} passed, {failed} failed, {unknown} unknown");
+    if failed > 0 {
+        return Err(format!("{failed} part(s) failed verification"));
+    }
+    Ok(())
+}
+
+/// Times parsing and each selected part separately; parts are solved against
+/// an input parsed once up front.
+fn bench(args: &BenchArgs) -> Result<(), String> {
+    let example = args.run.input == InputSource::Example;
+    for day in select_run_days(&args.run)? {
+        let path = args.run.input.resolve(day);
+        let text = fs::read(&path).map_err(|e| format!("{}: {e}", path.display()))?;
+        let input = (day.parse)(&text, example, &args.run.flags)
+            .map_err(|e| format!("{}: {e}", path.display()))?;
+        println!(
+            "day {} ({}, {} bytes)",
+            day.number,
+            path.display(),
+            text.len()
+        );
+
+        let stats = bench::measure(args.runs, || (day.parse)(&text, example, &args.run.flags));
+        let mib_per_second = text.len() as f64 * stats.per_second() / (1024.0 * 1024.0);
+        print_stats("parse", &stats, &format!("{mib_per_second:.1} MiB/s"));
+        for part in args.run.parts.iter() {
+            let stats = bench::measure(args.runs, || input.solve(*part));
+            let label = format!("part {}", part.number());
+            print_stats(&label, &stats, &format!("{:.1} runs/s", stats.per_second()));
+        }
+    }
+    Ok(())
+}
+
+fn print_stats(label: &str, stats: &Stats, throughput: &str) {
+    println!(
+        "  {label:<7} {:>6} runs  min {:>9}  median {:>9}  p95 {:>9}  {throughput}",
+        stats.runs,
+        Human(stats.min),
+        Human(stats.median),
+        Human(stats.p95),
+    );
+}
@@ -0,0 +1,94 @@
+use std::path::{Path, PathBuf};
+
+use aoc_common::{ParseError, Part, Solution};
+
+/// A day's parsed input with its `Solution` type erased, so the runner can
+/// hold any day behind the same pointer.
+pub trait Parsed {
+    fn solve(&self, part: Part) -> u64;
+}
+
+struct ParsedInput<S: Solution> {
+    input: S::Input,
+    part_1_params: S::Part1Params,
+    part_2_params: S::Part2Params,
+}
+
+impl<S: Solution> Parsed for ParsedInput<S> {
+    fn solve(&self, part: Part) -> u64 {
+        match part {
+            Part::One => S::part_1(&self.input, &self.part_1_params),
+            Part::Two => S::part_2(&self.input, &self.part_2_params),
+        }
+    }
+}
+
+fn parse<S: Solution + 'static>(
+    input: &[u8],
+    example: bool,
+    flags: &[String],
+) -> Result<Box<dyn Parsed>, ParseError> {
+    let (mut part_1_params, mut part_2_params) = if example {
+        (S::part_1_example_params(), S::part_2_example_params())
+    } else {
+        Default::default()
+    };
+    for flag in flags {
+        S::apply_flag(flag, &mut part_1_params, &mut part_2_params);
+    }
+    Ok(Box::new(ParsedInput::<S> {
+        input: S::parse(input)?,
+        part_1_params,
+        part_2_params,
+    }))
+}
+
+type ParseFn = fn(&[u8], bool, &[String]) -> Result<Box<dyn Parsed>, ParseError>;
+
+pub struct Day {
+    pub number: u8,
+    /// Parses an input file; `example` selects the parameters the puzzle
+    /// text uses for its worked example, which the flags then adjust.
+    pub parse: ParseFn,
+    /// The day-specific flags `parse` accepts.
+    pub flags: &'static [&'static str],
+}
+
+impl Day {
+    const fn of<S: Solution + 'static>() -> Self {
+        Self {
+            number: S::DAY,
+            parse: parse::<S>,
+            flags: S::FLAGS,
+        }
+    }
+
+    pub fn dir(&self) -> PathBuf {
+        workspace_root()
+            .join("days")
+            .join(format!("day{}", self.number))
+    }
+}
+
+pub fn workspace_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .ancestors()
+        .nth(2)
+        .expect("aoc lives two levels below the workspace root")
+}
+
+pub const DAYS: &[Day] = &[
+    Day::of::<day1::Day1>(),
+    Day::of::<day2::Day2>(),
+    Day::of::<day3::Day3>(),
+    Day::of::<day4::Day4>(),
+    Day::of::<day5::Day5>(),
+    Day::of::<day6::Day6>(),
+    Day::of::<day7::Day7>(),
+    Day::of::<day8::Day8>(),
+    Day::of::<day9::Day9>(),
+];
+
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
@@ -0,0 +1,272 @@
+use std::{fs, path::Path};
+
+const EDITION: &str = "2024";
+const LIB_TEMPLATE: &str = include_str!("../../../template/src/lib.rs");
+const MAIN_TEMPLATE: &str = include_str!("../../../template/src/main.rs");
+const REGISTRY_PATH: &str = "crates/aoc/src/registry.rs";
+const RUNNER_MANIFEST_PATH: &str = "crates/aoc/Cargo.toml";
+
+/// Creates `days/day<day>` from the template and registers it with the
+/// runner. Refuses to touch a day that already exists.
+pub fn new_day(root: &Path, day: u8) -> Result<(), String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("day must be between 1 and 25, got {day}"));
+    }
+    let dir = root.join("days").join(format!("day{day}"));
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()));
+    }
+    let registry_path = root.join(REGISTRY_PATH);
+    let manifest_path = root.join(RUNNER_MANIFEST_PATH);
+    let original_registry = read(&registry_path)?;
+    let manifest = read(&manifest_path)?;
+    let registry = register_day(&original_registry, day)?;
+    let manifest = register_dependency(&manifest, day)?;
+
+    // Built next to its final place and renamed in, so a failed write never
+    // leaves a half-created day behind.
+    let staging = root.join("days").join(format!(".day{day}.new"));
+    let _ = fs::remove_dir_all(&staging);
+    let created = write_day_files(&staging, day)
+        .and_then(|()| fs::rename(&staging, &dir).map_err(|e| format!("{}: {e}", dir.display())));
+    if let Err(message) = created {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(message);
+    }
+    let registered =
+        write(&registry_path, &registry).and_then(|()| write(&manifest_path, &manifest));
+    if let Err(message) = registered {
+        let _ = write(&registry_path, &original_registry);
+        let _ = fs::remove_dir_all(&dir);
+        return Err(message);
+    }
+    Ok(())
+}
+
+fn write_day_files(dir: &Path, day: u8) -> Result<(), String> {
+    fs::create_dir_all(dir.join("src")).map_err(|e| format!("{}: {e}", dir.display()))?;
+    write(&dir.join("Cargo.toml"), &manifest_for(day))?;
+    write(&dir.join("src/lib.rs"), &render(LIB_TEMPLATE, day))?;
+    write(&dir.join("src/main.rs"), &render(MAIN_TEMPLATE, day))?;
+    write(&dir.join("example.txt"), "")?;
+    // Matches the template's placeholder answers until `aoc extract` fills
+    // in the real ones.
+    write(
+        &dir.join("example_answers.toml"),
+        "part_1 = 0\npart_2 = 0\n",
+    )?;
+    write(&dir.join("input.txt"), "")
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    fs::write(path, contents).map_err(|e| format!("{}: {e}", path.display()))
+}
+
+fn manifest_for(day: u8) -> String {
+    format!(
+        "[package]
+name = \"day{day}\"
+version = \"0.1.0\"
+edition = \"{EDITION}\"
+
+[lints]
+workspace = true
+
+[dependencies]
+aoc-common = {{ path = \"../../crates/aoc-common\" }}
+"
+    )
+}
+
+fn render(template: &str, day: u8) -> String {
+    template
+        .replace("dayN", &format!("day{day}"))
+        .replace("DayN", &format!("Day{day}"))
+        .replace("const DAY: u8 = 0;", &format!("const DAY: u8 = {day};"))
+}
+
+/// Adds `Day::of::<dayN::DayN>()` to the `DAYS` table, keeping it in order.
+fn register_day(registry: &str, day: u8) -> Result<String, String> {
+    insert_in_order(
+        registry,
+        "pub const DAYS: &[Day] = &[",
+        "];",
+        &format!("    Day::of::<day{day}::Day{day}>(),"),
+        |line| number_after(line.trim_start(), "Day::of::<day"),
+        day,
+    )
+    .ok_or_else(|| format!("could not find the DAYS table in {REGISTRY_PATH}"))
+}
+
+/// Adds a `dayN = { path = ... }` dependency to the runner's manifest.
+fn register_dependency(manifest: &str, day: u8) -> Result<String, String> {
+    insert_in_order(
+        manifest,
+        "[dependencies]",
+        "[",
+        &format!("day{day} = {{ path = \"../../days/day{day}\" }}"),
+        |line| number_after(line, "day"),
+        day,
+    )
+    .ok_or_else(|| format!("could not find [dependencies] in {RUNNER_MANIFEST_PATH}"))
+}
+
+/// Inserts `new_line` into the block of lines after the `start` line (and
+/// before a line starting with `end`, or the end of the text), placing it
+/// after the last entry whose day number is smaller than `day`.
+fn insert_in_order(
+    text: &str,
+    start: &str,
+    end: &str,
+    new_line: &str,
+    day_of: impl Fn(&str) -> Option<u8>,
+    day: u8,
+) -> Option<String> {
+    let mut lines: Vec<&str> = text.lines().collect();
+    let start_index = lines.iter().position(|line| line.trim_end() == start)?;
+    let end_index = lines[start_index + 1..]
+        .iter()
+        .position(|line| line.starts_with(end))
+        .map_or(lines.len(), |i| start_index + 1 + i);
+    let mut insert_at = start_index + 1;
+    for (i, line) in lines
+        .iter()
+        .enumerate()
+        .take(end_index)
+        .skip(start_index + 1)
+    {
+        match day_of(line) {
+            Some(existing) if existing < day => insert_at = i + 1,
+            Some(_) => break,
+            None if insert_at == i => insert_at = i + 1,
+            None => {}
+        }
+    }
+    while insert_at > start_index + 1 && lines[insert_at - 1].trim().is_empty() {
+        insert_at -= 1;
+    }
+    lines.insert(insert_at, new_line);
+    let mut result = lines.join("\n");
+    if text.ends_with('\n') {
+        result.push('\n');
+    }
+    Some(result)
+}
+
+fn number_after(line: &str, prefix: &str) -> Option<u8> {
+    let rest = line.strip_prefix(prefix)?;
+    let digits: String = rest.chars().take_while(char::is_ascii_digit).collect();
+    digits.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    const MANIFEST: &str = "[package]
+name = \"aoc\"
+
+[dependencies]
+aoc-common = { path = \"../aoc-common\" }
+day1 = { path = \"../../days/day1\" }
+day3 = { path = \"../../days/day3\" }
+";
+
+    const REGISTRY: &str = "pub const DAYS: &[Day] = &[
+    Day::of::<day1::Day1>(),
+    Day::of::<day3::Day3>(),
+];
+";
+
+    #[test]
+    fn test_register_dependency_in_order() {
+        let manifest = register_dependency(MANIFEST, 2).unwrap();
+        assert!(manifest.contains(
+            "day1 = { path = \"../../days/day1\" }
+day2 = { path = \"../../days/day2\" }
+day3 = { path = \"../../days/day3\" }
+"
+        ));
+        let manifest = register_dependency(MANIFEST, 10).unwrap();
+        assert!(manifest.ends_with("day10 = { path = \"../../days/day10\" }\n"));
+    }
+
+    #[test]
+    fn test_register_day_in_order() {
+        let registry = register_day(REGISTRY, 2).unwrap();
+        assert_eq!(
+            registry,
+            "pub const DAYS: &[Day] = &[
+    Day::of::<day1::Day1>(),
+    Day::of::<day2::Day2>(),
+    Day::of::<day3::Day3>(),
+];
+"
+        );
+        assert!(register_day("fn main() {}", 2).is_err());
+    }
+
+    #[test]
+    fn test_render_template() {
+        let lib = render(LIB_TEMPLATE, 12);
+        assert!(lib.contains("pub struct Day12;"));
+        assert!(lib.contains("impl Solution for Day12 {"));
+        assert!(lib.contains("const DAY: u8 = 12;"));
+        // Returning the exit code so parse errors fail the process.
+        assert_eq!(
+            render(MAIN_TEMPLATE, 12),
+            "use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    aoc_common::run::<day12::Day12>()
+}
+"
+        );
+    }
+
+    #[test]
+    fn test_new_day_creates_and_refuses_to_clobber() {
+        let root = env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("crates/aoc/src")).unwrap();
+        fs::write(root.join(REGISTRY_PATH), REGISTRY).unwrap();
+        fs::write(root.join(RUNNER_MANIFEST_PATH), MANIFEST).unwrap();
+
+        new_day(&root, 4).unwrap();
+        let day_dir = root.join("days/day4");
+        assert!(
+            read(&day_dir.join("Cargo.toml"))
+                .unwrap()
+                .contains("name = \"day4\"")
+        );
+        assert_eq!(read(&day_dir.join("input.txt")).unwrap(), "");
+        assert!(
+            read(&root.join(REGISTRY_PATH))
+                .unwrap()
+                .contains("day4::Day4")
+        );
+
+        fs::write(day_dir.join("input.txt"), "keep me").unwrap();
+        assert!(new_day(&root, 4).is_err());
+        assert_eq!(read(&day_dir.join("input.txt")).unwrap(), "keep me");
+        assert!(new_day(&root, 26).is_err());
+
+        // A write that fails part way leaves neither the day nor its
+        // registration behind.
+        fs::write(root.join("days/.day5.new"), "in the way").unwrap();
+        assert!(new_day(&root, 5).is_err());
+        assert!(!root.join("days/day5").exists());
+        assert!(
+            !read(&root.join(REGISTRY_PATH))
+                .unwrap()
+                .contains("day5::Day5")
+        );
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
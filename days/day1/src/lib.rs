@@ -0,0 +1,276 @@
+use std::io::BufRead;
+
+use aoc_common::{ParseError, Solution, parse};
+pub use trace::{Step, trace, trace_csv, trace_json_lines};
+
+mod trace;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    type Input = Input;
+    type Part1Params = Dial;
+    type Part2Params = Dial;
+
+    fn parse(input: impl BufRead) -> Result<Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Input, dial: &Dial) -> u64 {
+        part_1(input, dial.clone())
+    }
+
+    fn part_2(input: &Input, dial: &Dial) -> u64 {
+        part_2(input, dial.clone())
+    }
+}
+
+/// Signed rotations, right positive. Each amount fits in an `i64`.
+pub type Input = Vec<i64>;
+
+fn parse_input(input: impl BufRead) -> Result<Input, ParseError> {
+    let text = parse::read_to_string(input)?;
+    let mut rotations = Vec::new();
+    for (line_number, line) in parse::numbered_lines(&text) {
+        let mut chars = line.chars();
+        let direction = match chars.next() {
+            Some('L') => -1,
+            Some('R') => 1,
+            other => {
+                let found = other.map(String::from).unwrap_or_default();
+                return Err(ParseError::new(line_number, 1, found, "`L` or `R`"));
+            }
+        };
+        let amount = chars.as_str();
+        let invalid = |expected| ParseError::new(line_number, 2, amount, expected);
+        // The direction is the only sign; `u64` parsing would take a `+`.
+        if !amount.starts_with(|c: char| c.is_ascii_digit()) {
+            return Err(invalid("a rotation amount"));
+        }
+        let clicks: u64 = parse::field(line_number, 2, amount, "a rotation amount")?;
+        let clicks = i64::try_from(clicks)
+            .map_err(|_| invalid("a rotation amount up to 9223372036854775807"))?;
+        rotations.push(clicks * direction);
+    }
+    Ok(rotations)
+}
+
+fn part_1(input: &Input, dial: Dial) -> u64 {
+    trace(input, dial).filter(|step| step.end == 0).count() as u64
+}
+
+/// Each rotation passes 0 fewer than 2^63 times, but enough of them can
+/// add up past a `u64`, so the sum is taken in a `u128` and saturates.
+fn part_2(input: &Input, dial: Dial) -> u64 {
+    let zero_passes: u128 = trace(input, dial)
+        .map(|step| u128::from(step.zero_passes))
+        .sum();
+    u64::try_from(zero_passes).unwrap_or(u64::MAX)
+}
+
+/// A dial numbered `0..size` with a pointer at `position`; rotating right
+/// counts up and wraps from `size - 1` to 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dial {
+    size: u32,
+    position: u32,
+}
+
+impl Default for Dial {
+    fn default() -> Self {
+        Self::new(100, 50)
+    }
+}
+
+impl Dial {
+    /// # Panics
+    ///
+    /// If `size` is 0 or `start` isn't on the dial.
+    pub fn new(size: u32, start: u32) -> Self {
+        assert!(
+            start < size,
+            "start {start} is not on a dial of size {size}"
+        );
+        Self {
+            size,
+            position: start,
+        }
+    }
+
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    pub fn position(&self) -> u32 {
+        self.position
+    }
+
+    /// Turns the dial `clicks` to the right, or left if negative. Returns the
+    /// new position and how many clicks left the pointer at 0, including
+    /// the last one but not the starting position.
+    pub fn rotate(&mut self, clicks: i64) -> (u32, u64) {
+        // The distance is at most 2^63 and the position below 2^32, so
+        // their sum can't overflow a u64.
+        let size = u64::from(self.size);
+        let position = u64::from(self.position);
+        let distance = clicks.unsigned_abs();
+        // Turning left from p hits 0 where turning right from size - p
+        // would, so both directions count from a position measured in
+        // the direction of travel.
+        let travelled = if clicks >= 0 {
+            position
+        } else {
+            (size - position) % size
+        };
+        let zero_passes = (travelled + distance) / size;
+        let step = distance % size;
+        let next = if clicks >= 0 {
+            (position + step) % size
+        } else {
+            (position + size - step) % size
+        };
+        self.position = next as u32;
+        (self.position, zero_passes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::Answers;
+
+    use super::*;
+    const EXAMPLE: &str = include_str!("../example.txt");
+    const EXAMPLE_ANSWERS: &str = include_str!("../example_answers.toml");
+
+    macro_rules! part_2_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (input, expected) = $value;
+                let input = parse_input(input.as_bytes()).unwrap();
+                assert_eq!(expected, part_2(&input, Dial::default()));
+            }
+        )*
+        }
+    }
+
+    #[test]
+    fn test_parse_error_location() {
+        let err = parse_input("L68\nR12\nX30".as_bytes()).unwrap_err();
+        assert_eq!(err.position(), Some((3, 1)));
+        let err = parse_input("L68\nR1x".as_bytes()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 2: expected a rotation amount, found \"1x\""
+        );
+    }
+
+    #[test]
+    fn test_part_1_example() {
+        let input = parse_input(EXAMPLE.as_bytes()).unwrap();
+        let expected = Answers::parse(EXAMPLE_ANSWERS).unwrap();
+        assert_eq!(Some(part_1(&input, Dial::default())), expected.part_1);
+    }
+
+    #[test]
+    fn test_part_2_example() {
+        let input = parse_input(EXAMPLE.as_bytes()).unwrap();
+        let expected = Answers::parse(EXAMPLE_ANSWERS).unwrap();
+        assert_eq!(Some(part_2(&input, Dial::default())), expected.part_2);
+    }
+
+    part_2_tests! {
+        test_part_2_right_end_at_zero: ("R50", 1),
+        test_part_2_right_past_zero: ("R150", 2),
+        test_part_2_left_end_at_zero: ("L50", 1),
+        test_part_2_left_past_zero: ("L150", 2),
+        test_part_2_left_zero_to_zero: ("L50\nL100", 2),
+        test_part_2_right_zero_to_zero: ("R50\nR100", 2),
+        test_part_2_right_left_zero_to_zero: ("R50\nL100", 2),
+        test_part_2_left_right_zero_to_zero: ("L50\nR100", 2),
+    }
+
+    /// Turns `dial` one click at a time, counting every click that lands on 0.
+    fn rotate_by_clicks(dial: &mut Dial, clicks: i64) -> (u32, u64) {
+        let size = dial.size();
+        let mut position = dial.position();
+        let mut zero_passes = 0;
+        for _ in 0..clicks.unsigned_abs() {
+            position = if clicks > 0 {
+                (position + 1) % size
+            } else {
+                (position + size - 1) % size
+            };
+            if position == 0 {
+                zero_passes += 1;
+            }
+        }
+        *dial = Dial::new(size, position);
+        (position, zero_passes)
+    }
+
+    #[test]
+    fn test_rotate_matches_click_simulation() {
+        // A small LCG keeps the test deterministic without a rand dependency.
+        let mut state = 0x9e37_79b9_7f4a_7c15_u64;
+        let mut next = move |bound: u32| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+            ((state >> 33) % u64::from(bound)) as u32
+        };
+        for _ in 0..200 {
+            let size = 1 + next(30);
+            let start = next(size);
+            let mut dial = Dial::new(size, start);
+            let mut simulated = dial.clone();
+            for _ in 0..50 {
+                let clicks = i64::from(next(8 * size + 1)) - 4 * i64::from(size);
+                assert_eq!(
+                    dial.rotate(clicks),
+                    rotate_by_clicks(&mut simulated, clicks),
+                    "size {size}, start {start}, clicks {clicks}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_configured_dial() {
+        let input = parse_input("R3\nL1\nL5".as_bytes()).unwrap();
+        assert_eq!(part_1(&input, Dial::new(4, 1)), 1);
+        assert_eq!(part_2(&input, Dial::new(4, 1)), 2);
+        assert_eq!(Dial::new(1, 0).rotate(-3), (0, 3));
+    }
+
+    #[test]
+    fn test_extreme_rotations() {
+        let input = parse_input("R9223372036854775807\nL9223372036854775807".as_bytes()).unwrap();
+        assert_eq!(input, vec![i64::MAX, -i64::MAX]);
+        let err = parse_input("R9223372036854775808".as_bytes()).unwrap_err();
+        assert_eq!(err.position(), Some((1, 2)));
+        for signed in ["L-50", "R-1", "R+5"] {
+            let err = parse_input(signed.as_bytes()).unwrap_err();
+            assert_eq!(err.position(), Some((1, 2)), "{signed}");
+        }
+        let input =
+            parse_input("R9223372036854775807\nL9223372036854775807\nR1".as_bytes()).unwrap();
+        assert_eq!(part_2(&input, Dial::new(1, 0)), u64::MAX);
+        let input = parse_input("R9223372036854775807\n".repeat(3).as_bytes()).unwrap();
+        assert_eq!(input, vec![i64::MAX; 3]);
+        assert_eq!(part_2(&input, Dial::new(1, 0)), u64::MAX);
+
+        // 2^63 - 1 = 100 * 92233720368547758 + 7.
+        assert_eq!(Dial::new(100, 50).rotate(i64::MAX), (57, 92233720368547758));
+        assert_eq!(
+            Dial::new(100, 50).rotate(-i64::MAX),
+            (43, 92233720368547758)
+        );
+        assert_eq!(Dial::new(100, 0).rotate(i64::MIN), (92, 92233720368547758));
+        assert_eq!(
+            Dial::new(u32::MAX, u32::MAX - 1).rotate(i64::MAX),
+            (2147483646, 2147483649)
+        );
+    }
+}
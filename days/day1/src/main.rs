@@ -1,97 +1,55 @@
-use std::io::{stdin, BufRead};
+use std::{env, process::ExitCode};
 
-fn main() {
-    let input = parse_input(stdin().lock());
-    println!("part 1: {}", part_1(&input));
-    println!("part 2: {}", part_2(&input));
-}
-
-type Input = Vec<i32>;
+use aoc_common::Solution;
+use day1::{Day1, Dial};
 
-fn parse_input(mut input: impl BufRead) -> Input {
-    let mut buf = String::new();
-    input.read_to_string(&mut buf).unwrap();
-    buf.lines()
-        .map(|line| {
-            let (direction_str, value_str) = line.split_at(1);
-            let direction = match direction_str {
-                "L" => -1,
-                "R" => 1,
-                _ => panic!(),
-            };
-            let value: i32 = value_str.parse().unwrap();
-            value * direction
-        })
-        .collect()
-}
+const USAGE: &str = "usage: day1 --trace <csv|json> [<input>]";
 
-fn part_1(input: &Input) -> u64 {
-    input
-        .iter()
-        .scan(50, |state, &x| {
-            *state = (*state + x).rem_euclid(100);
-            Some(*state)
-        })
-        .map(|state| if state == 0 { 1 } else { 0 })
-        .sum()
-}
-
-fn part_2(input: &Input) -> u64 {
-    input
-        .iter()
-        .scan(50, |state, &x| {
-            let started_at_zero = *state == 0;
-            let next_absolute = *state + x;
-            *state = (next_absolute).rem_euclid(100);
-            let adjustment = if !started_at_zero && next_absolute <= 0 {
-                1
-            } else {
-                0
-            };
-            let div = next_absolute.abs().div_euclid(100) as u64;
-            Some(div + adjustment)
-        })
-        .sum()
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.iter().any(|arg| arg == "--trace") {
+        return aoc_common::run::<Day1>();
+    }
+    match print_trace(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("error: {message}");
+            ExitCode::FAILURE
+        }
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    const EXAMPLE: &str = include_str!("../example.txt");
-
-    macro_rules! part_2_tests {
-        ($($name:ident: $value:expr,)*) => {
-        $(
-            #[test]
-            fn $name() {
-                let (input, expected) = $value;
-                let input = parse_input(input.as_bytes());
-                assert_eq!(expected, part_2(&input));
+/// Prints every rotation with its start, end and zero passes instead of
+/// the answers.
+fn print_trace(args: &[String]) -> Result<(), String> {
+    let mut json = None;
+    let mut input = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--trace" => {
+                let format = args
+                    .next()
+                    .ok_or(format!("--trace needs a format\n\n{USAGE}"))?;
+                json = Some(match format.as_str() {
+                    "csv" => false,
+                    "json" => true,
+                    _ => return Err(format!("invalid trace format: {format}\n\n{USAGE}")),
+                });
             }
-        )*
+            path if input.is_none() && !path.starts_with("--") => input = Some(path),
+            _ => return Err(format!("unexpected argument: {arg}\n\n{USAGE}")),
         }
     }
-
-    #[test]
-    fn test_part_1_example() {
-        let input = parse_input(EXAMPLE.as_bytes());
-        assert_eq!(part_1(&input), 3);
-    }
-
-    #[test]
-    fn test_part_2_example() {
-        let input = parse_input(EXAMPLE.as_bytes());
-        assert_eq!(part_2(&input), 6);
-    }
-
-    part_2_tests! {
-        test_part_2_right_end_at_zero: ("R50", 1),
-        test_part_2_right_past_zero: ("R150", 2),
-        test_part_2_left_end_at_zero: ("L50", 1),
-        test_part_2_left_past_zero: ("L150", 2),
-        test_part_2_left_zero_to_zero: ("L50\nL100", 2),
-        test_part_2_right_zero_to_zero: ("R50\nR100", 2),
-        test_part_2_right_left_zero_to_zero: ("R50\nL100", 2),
-        test_part_2_left_right_zero_to_zero: ("L50\nR100", 2),
+    let json = json.ok_or(USAGE)?;
+    let name = input.unwrap_or("-");
+    let text = aoc_common::read_input(input)?;
+    let input = Day1::parse(text.as_slice()).map_err(|e| format!("{name}: {e}"))?;
+    let steps = day1::trace(&input, Dial::default());
+    if json {
+        print!("{}", day1::trace_json_lines(steps));
+    } else {
+        print!("{}", day1::trace_csv(steps));
     }
+    Ok(())
 }
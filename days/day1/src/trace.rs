@@ -0,0 +1,91 @@
+//! A record of every rotation, for checking the zero passes instruction by
+//! instruction rather than only through the totals.
+
+use std::fmt::Write;
+
+use crate::{Dial, Input};
+
+/// One rotation of the dial.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    /// The input line the rotation came from, counting from 1.
+    pub line: usize,
+    /// Clicks to the right, or to the left if negative.
+    pub clicks: i64,
+    pub start: u32,
+    pub end: u32,
+    pub zero_passes: u64,
+}
+
+/// The rotations in `input` applied in turn to `dial`.
+pub fn trace(input: &Input, mut dial: Dial) -> impl Iterator<Item = Step> + '_ {
+    input.iter().enumerate().map(move |(i, &clicks)| {
+        let start = dial.position();
+        let (end, zero_passes) = dial.rotate(clicks);
+        Step {
+            line: i + 1,
+            clicks,
+            start,
+            end,
+            zero_passes,
+        }
+    })
+}
+
+/// The steps as CSV, with a header row.
+pub fn trace_csv(steps: impl IntoIterator<Item = Step>) -> String {
+    let mut out = String::from("line,clicks,start,end,zero_passes\n");
+    for step in steps {
+        writeln!(
+            out,
+            "{},{},{},{},{}",
+            step.line, step.clicks, step.start, step.end, step.zero_passes
+        )
+        .expect("writing to a String cannot fail");
+    }
+    out
+}
+
+/// The steps as JSON Lines, one object per step.
+pub fn trace_json_lines(steps: impl IntoIterator<Item = Step>) -> String {
+    let mut out = String::new();
+    for step in steps {
+        writeln!(
+            out,
+            r#"{{"line":{},"clicks":{},"start":{},"end":{},"zero_passes":{}}}"#,
+            step.line, step.clicks, step.start, step.end, step.zero_passes
+        )
+        .expect("writing to a String cannot fail");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_input;
+
+    #[test]
+    fn test_trace_formats() {
+        let input = parse_input("L68\nR18\nL50".as_bytes()).unwrap();
+        let steps: Vec<Step> = trace(&input, Dial::default()).collect();
+        assert_eq!(
+            steps[1],
+            Step {
+                line: 2,
+                clicks: 18,
+                start: 82,
+                end: 0,
+                zero_passes: 1,
+            }
+        );
+        assert_eq!(
+            trace_csv(steps.clone()),
+            "line,clicks,start,end,zero_passes\n1,-68,50,82,1\n2,18,82,0,1\n3,-50,0,50,0\n"
+        );
+        assert_eq!(
+            trace_json_lines(steps).lines().nth(2),
+            Some(r#"{"line":3,"clicks":-50,"start":0,"end":50,"zero_passes":0}"#)
+        );
+    }
+}
@@ -0,0 +1,287 @@
+use std::{io::BufRead, ops::RangeInclusive};
+
+use aoc_common::{ParseError, Solution, parse, ranges};
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+
+    type Input = Input;
+    type Part1Params = Params;
+    type Part2Params = Params;
+
+    fn parse(input: impl BufRead) -> Result<Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Input, params: &Params) -> u64 {
+        part_1(&ranges_to_count(input, params))
+    }
+
+    fn part_2(input: &Input, params: &Params) -> u64 {
+        part_2(&ranges_to_count(input, params))
+    }
+
+    const FLAGS: &'static [&'static str] = &["--per-range"];
+
+    fn apply_flag(_flag: &str, part_1: &mut Params, part_2: &mut Params) {
+        part_1.per_range = true;
+        part_2.per_range = true;
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Params {
+    /// Sum each range on its own, so an ID in several overlapping ranges is
+    /// counted once per range, rather than merging the ranges first.
+    pub per_range: bool,
+}
+
+fn ranges_to_count(input: &Input, params: &Params) -> Input {
+    if params.per_range {
+        input.clone()
+    } else {
+        ranges::merge(input)
+    }
+}
+
+/// Two input ranges that share IDs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overlap {
+    /// Positions of the two ranges in the input, counting from 0, `first`
+    /// before `second`.
+    pub first: usize,
+    pub second: usize,
+    pub shared: RangeInclusive<u64>,
+}
+
+/// Every pair of input ranges that share IDs, which merging counts once
+/// and `per_range` counts once per range.
+pub fn overlaps(input: &Input) -> Vec<Overlap> {
+    let mut overlaps = Vec::new();
+    for (first, a) in input.iter().enumerate() {
+        for (second, b) in input.iter().enumerate().skip(first + 1) {
+            let shared = *a.start().max(b.start())..=*a.end().min(b.end());
+            if !shared.is_empty() {
+                overlaps.push(Overlap {
+                    first,
+                    second,
+                    shared,
+                });
+            }
+        }
+    }
+    overlaps
+}
+
+pub type Input = Vec<RangeInclusive<u64>>;
+
+fn parse_input(input: impl BufRead) -> Result<Input, ParseError> {
+    let text = parse::read_to_string(input)?;
+    let mut ranges = Vec::new();
+    for (line_number, line) in parse::numbered_lines(&text) {
+        for (column, raw_range) in parse::fields(line, ',') {
+            let Some((low, high)) = raw_range.split_once('-') else {
+                return Err(ParseError::new(
+                    line_number,
+                    column,
+                    raw_range,
+                    "a range like `11-22`",
+                ));
+            };
+            let high_column = column + low.chars().count() + 1;
+            let low = parse::field(line_number, column, low, "a range start")?;
+            let high = parse::field(line_number, high_column, high, "a range end")?;
+            ranges.push(low..=high);
+        }
+    }
+    Ok(ranges)
+}
+
+/// Digits in `u64::MAX`, the longest ID there can be.
+const MAX_DIGITS: u32 = 20;
+
+fn part_1(input: &Input) -> u64 {
+    total(input.iter().map(|range| {
+        (2..=MAX_DIGITS)
+            .step_by(2)
+            .map(|length| sum_repeating(range, length, length / 2))
+            .sum()
+    }))
+}
+
+fn part_2(input: &Input) -> u64 {
+    total(input.iter().map(sum_any_repeating))
+}
+
+fn total(sums: impl Iterator<Item = u128>) -> u64 {
+    u64::try_from(sums.sum::<u128>()).expect("sum of invalid IDs overflows u64")
+}
+
+/// Sum of the IDs in `range` that are some pattern repeated at least twice.
+///
+/// A `length`-digit ID repeating a `period`-digit pattern also repeats one
+/// for every multiple of `period` that divides `length`, so every such ID
+/// repeats a pattern of `length / q` digits for some prime `q` dividing
+/// `length`. Those sets overlap exactly where the periods' gcd repeats, so
+/// inclusion–exclusion over the primes counts each ID once.
+fn sum_any_repeating(range: &RangeInclusive<u64>) -> u128 {
+    let mut sum: i128 = 0;
+    for length in 2..=MAX_DIGITS {
+        let primes: Vec<u32> = (2..=length)
+            .filter(|&q| length.is_multiple_of(q) && (2..q).all(|d| !q.is_multiple_of(d)))
+            .collect();
+        for subset in 1..1u32 << primes.len() {
+            let product: u32 = (0..primes.len())
+                .filter(|&i| subset & (1 << i) != 0)
+                .map(|i| primes[i])
+                .product();
+            let term = sum_repeating(range, length, length / product) as i128;
+            if subset.count_ones() % 2 == 1 {
+                sum += term;
+            } else {
+                sum -= term;
+            }
+        }
+    }
+    sum as u128
+}
+
+/// Sum of the `length`-digit IDs in `range` made of one `period`-digit
+/// pattern repeated. Those are `pattern × (10^length − 1) / (10^period − 1)`,
+/// so they form an arithmetic series over the patterns that land in range.
+fn sum_repeating(range: &RangeInclusive<u64>, length: u32, period: u32) -> u128 {
+    let multiplier = (10u128.pow(length) - 1) / (10u128.pow(period) - 1);
+    let low = (*range.start() as u128).max(10u128.pow(length - 1));
+    let high = (*range.end() as u128).min(10u128.pow(length) - 1);
+    if low > high {
+        return 0;
+    }
+    let first = low.div_ceil(multiplier).max(10u128.pow(period - 1));
+    let last = (high / multiplier).min(10u128.pow(period) - 1);
+    if first > last {
+        return 0;
+    }
+    multiplier * (first + last) * (last - first + 1) / 2
+}
+
+/// Part 1 by checking every ID in every range; kept as a reference for the
+/// arithmetic version.
+pub fn part_1_brute_force(input: &Input) -> u64 {
+    input
+        .iter()
+        .flat_map(|range| range.clone())
+        .filter(|x| id_is_invalid(*x))
+        .sum()
+}
+
+fn id_is_invalid(id: u64) -> bool {
+    let id_string = id.to_string();
+    if !id_string.len().is_multiple_of(2) {
+        return false;
+    }
+    let (lhs, rhs) = id_string.split_at(id_string.len() / 2);
+    lhs == rhs
+}
+
+/// Part 2 by checking every ID in every range; kept as a reference for the
+/// arithmetic version.
+pub fn part_2_brute_force(input: &Input) -> u64 {
+    input
+        .iter()
+        .flat_map(|range| range.clone())
+        .filter(|x| id_is_invalid_part_2(*x))
+        .sum()
+}
+
+fn id_is_invalid_part_2(id: u64) -> bool {
+    let id_string = id.to_string();
+    for pattern_len in 1..=id_string.len() / 2 {
+        let bytes = id_string.as_bytes();
+        let first = &bytes[0..pattern_len];
+        if bytes.chunks(pattern_len).all(|chunk| chunk == first) {
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::Answers;
+
+    use super::*;
+    const EXAMPLE: &str = include_str!("../example.txt");
+    const EXAMPLE_ANSWERS: &str = include_str!("../example_answers.toml");
+
+    #[test]
+    fn test_parse_error_location() {
+        let err = parse_input("11-22,95-x15".as_bytes()).unwrap_err();
+        assert_eq!(err.position(), Some((1, 10)));
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let input = vec![
+            1..=250_000,
+            999_990..=1_010_101,
+            123_123_000..=123_200_000,
+            7..=7,
+            1_188_511_880..=1_188_511_890,
+        ];
+        assert_eq!(part_1(&input), part_1_brute_force(&input));
+        assert_eq!(part_2(&input), part_2_brute_force(&input));
+    }
+
+    #[test]
+    fn test_huge_range() {
+        // Every 10 digit ID: far too many to check one at a time.
+        let input = vec![1_000_000_000..=9_999_999_999];
+        assert_eq!(part_1(&input), 495_000_449_955_000);
+        assert_eq!(part_2(&input), 495_445_904_500_410);
+    }
+
+    #[test]
+    fn test_overlapping_ranges_count_once() {
+        let input = parse_input("95-115,11-22,100-120,11-22".as_bytes()).unwrap();
+        let merged = Params::default();
+        let per_range = Params { per_range: true };
+        assert_eq!(Day2::part_1(&input, &merged), 11 + 22 + 99);
+        assert_eq!(Day2::part_1(&input, &per_range), 2 * (11 + 22) + 99);
+        assert_eq!(Day2::part_2(&input, &merged), 11 + 22 + 99 + 111);
+        assert_eq!(
+            Day2::part_2(&input, &per_range),
+            2 * (11 + 22) + 99 + 2 * 111
+        );
+        assert_eq!(
+            overlaps(&input),
+            vec![
+                Overlap {
+                    first: 0,
+                    second: 2,
+                    shared: 100..=115,
+                },
+                Overlap {
+                    first: 1,
+                    second: 3,
+                    shared: 11..=22,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_part_1_example() {
+        let input = parse_input(EXAMPLE.as_bytes()).unwrap();
+        let expected = Answers::parse(EXAMPLE_ANSWERS).unwrap();
+        assert_eq!(Some(part_1(&input)), expected.part_1);
+    }
+
+    #[test]
+    fn test_part_2_example() {
+        let input = parse_input(EXAMPLE.as_bytes()).unwrap();
+        let expected = Answers::parse(EXAMPLE_ANSWERS).unwrap();
+        assert_eq!(Some(part_2(&input)), expected.part_2);
+    }
+}
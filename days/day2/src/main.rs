@@ -1,80 +1,52 @@
-use std::{
-    io::{stdin, BufRead},
-    ops::RangeInclusive,
-};
+use std::{env, process::ExitCode};
 
-fn main() {
-    let input = parse_input(stdin().lock());
-    println!("part 1: {}", part_1(&input));
-    println!("part 2: {}", part_2(&input));
-}
-
-type Input = Vec<RangeInclusive<u64>>;
-
-fn parse_input(mut input: impl BufRead) -> Input {
-    let mut text = String::new();
-    input.read_to_string(&mut text).unwrap();
-    text.split(",")
-        .map(|raw_range| {
-            let (low, high) = raw_range.split_once('-').unwrap();
-            let low = low.parse().unwrap();
-            let high = high.parse().unwrap();
-            low..=high
-        })
-        .collect()
-}
+use aoc_common::Solution;
+use day2::Day2;
 
-fn part_1(input: &Input) -> u64 {
-    input
-        .iter()
-        .flat_map(|range| range.clone().into_iter())
-        .filter(|x| id_is_invalid(*x))
-        .sum()
-}
+const USAGE: &str = "usage: day2 --overlaps [<input>]";
 
-fn id_is_invalid(id: u64) -> bool {
-    let id_string = id.to_string();
-    if id_string.len() % 2 != 0 {
-        return false;
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.iter().any(|arg| arg == "--overlaps") {
+        return aoc_common::run::<Day2>();
     }
-    let (lhs, rhs) = id_string.split_at(id_string.len() / 2);
-    lhs == rhs
-}
-
-fn part_2(input: &Input) -> u64 {
-    input
-        .iter()
-        .flat_map(|range| range.clone().into_iter())
-        .filter(|x| id_is_invalid_part_2(*x))
-        .sum()
-}
-
-fn id_is_invalid_part_2(id: u64) -> bool {
-    let id_string = id.to_string();
-    for pattern_len in 1..=id_string.len() / 2 {
-        let bytes = id_string.as_bytes();
-        let first = &bytes[0..pattern_len];
-        if bytes.chunks(pattern_len).all(|chunk| chunk == first) {
-            return true;
+    match print_overlaps(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("error: {message}");
+            ExitCode::FAILURE
         }
     }
-    return false;
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    const EXAMPLE: &str = include_str!("../example.txt");
-
-    #[test]
-    fn test_part_1_example() {
-        let input = parse_input(EXAMPLE.as_bytes());
-        assert_eq!(part_1(&input), 1227775554);
+/// Lists which ranges overlap instead of solving.
+fn print_overlaps(args: &[String]) -> Result<(), String> {
+    let rest: Vec<&String> = args.iter().filter(|arg| *arg != "--overlaps").collect();
+    let input = match rest.as_slice() {
+        [] => None,
+        [path] if !path.starts_with("--") => Some(path.as_str()),
+        _ => return Err(format!("--overlaps takes only an input\n\n{USAGE}")),
+    };
+    let name = input.unwrap_or("-");
+    let text = aoc_common::read_input(input)?;
+    let ranges = Day2::parse(text.as_slice()).map_err(|e| format!("{name}: {e}"))?;
+    let overlaps = day2::overlaps(&ranges);
+    if overlaps.is_empty() {
+        println!("no ranges overlap");
     }
-
-    #[test]
-    fn test_part_2_example() {
-        let input = parse_input(EXAMPLE.as_bytes());
-        assert_eq!(part_2(&input), 4174379265);
+    for overlap in overlaps {
+        let (first, second) = (&ranges[overlap.first], &ranges[overlap.second]);
+        println!(
+            "range {} ({}-{}) and range {} ({}-{}) share {}-{}",
+            overlap.first + 1,
+            first.start(),
+            first.end(),
+            overlap.second + 1,
+            second.start(),
+            second.end(),
+            overlap.shared.start(),
+            overlap.shared.end(),
+        );
     }
+    Ok(())
 }
@@ -0,0 +1,170 @@
+use std::io::BufRead;
+
+use aoc_common::{ParseError, Solution, parse};
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    type Input = Input;
+    type Part1Params = Part1Params;
+    type Part2Params = Part2Params;
+
+    fn parse(input: impl BufRead) -> Result<Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Input, params: &Part1Params) -> u64 {
+        total_joltage(input, params.batteries)
+    }
+
+    fn part_2(input: &Input, params: &Part2Params) -> u64 {
+        total_joltage(input, params.batteries)
+    }
+}
+
+pub struct Part1Params {
+    /// How many batteries to turn on in each bank.
+    pub batteries: usize,
+}
+
+impl Default for Part1Params {
+    fn default() -> Self {
+        Self { batteries: 2 }
+    }
+}
+
+pub struct Part2Params {
+    /// How many batteries to turn on in each bank.
+    pub batteries: usize,
+}
+
+impl Default for Part2Params {
+    fn default() -> Self {
+        Self { batteries: 12 }
+    }
+}
+
+pub type Input = Vec<Vec<u32>>;
+
+fn parse_input(input: impl BufRead) -> Result<Input, ParseError> {
+    let text = parse::read_to_string(input)?;
+    parse::numbered_lines(&text)
+        .map(|(line_number, line)| {
+            if line.is_empty() {
+                return Err(ParseError::new(line_number, 1, "", "a bank of digits"));
+            }
+            line.chars()
+                .enumerate()
+                .map(|(i, c)| {
+                    c.to_digit(10)
+                        .ok_or_else(|| ParseError::new(line_number, i + 1, c, "a digit"))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn total_joltage(input: &Input, batteries: usize) -> u64 {
+    input
+        .iter()
+        .map(|bank| {
+            joltage(bank, batteries)
+                .expect("bank has fewer batteries than requested")
+                .value
+        })
+        .sum()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Joltage {
+    /// The chosen digits read in bank order.
+    pub value: u64,
+    /// Positions of the chosen batteries in the bank, increasing.
+    pub indices: Vec<usize>,
+}
+
+/// The largest joltage from turning on `k` batteries of `bank`, or `None`
+/// if the bank has fewer than `k`. Among equal values the leftmost
+/// batteries are chosen.
+///
+/// A single pass with a stack: a battery knocks smaller ones off the top
+/// while there are still batteries to spare, so what remains is the
+/// largest digit sequence that keeps the bank's order.
+///
+/// # Panics
+///
+/// If `k` is more than 19, as the value wouldn't fit in a `u64`.
+pub fn joltage(bank: &[u32], k: usize) -> Option<Joltage> {
+    assert!(k <= 19, "{k} digits don't fit in a u64");
+    let mut spare = bank.len().checked_sub(k)?;
+    let mut stack: Vec<usize> = Vec::with_capacity(bank.len());
+    for (i, &digit) in bank.iter().enumerate() {
+        while spare > 0 && stack.last().is_some_and(|&top| bank[top] < digit) {
+            stack.pop();
+            spare -= 1;
+        }
+        stack.push(i);
+    }
+    stack.truncate(k);
+    let value = stack
+        .iter()
+        .fold(0, |value, &i| value * 10 + bank[i] as u64);
+    Some(Joltage {
+        value,
+        indices: stack,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::Answers;
+
+    use super::*;
+    const EXAMPLE: &str = include_str!("../example.txt");
+    const EXAMPLE_ANSWERS: &str = include_str!("../example_answers.toml");
+
+    #[test]
+    fn test_parse_error_location() {
+        let err = parse_input("987\n81a".as_bytes()).unwrap_err();
+        assert_eq!(err.position(), Some((2, 3)));
+    }
+
+    #[test]
+    fn test_joltage() {
+        let bank: Vec<u32> = [8, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 9].into();
+        assert_eq!(
+            joltage(&bank, 2),
+            Some(Joltage {
+                value: 89,
+                indices: vec![0, 14],
+            })
+        );
+        assert_eq!(joltage(&bank, 12).unwrap().value, 811_111_111_119);
+        assert_eq!(joltage(&[2, 3, 3, 1], 2).unwrap().indices, vec![1, 2]);
+        assert_eq!(joltage(&[5, 5, 5], 2).unwrap().indices, vec![0, 1]);
+        assert_eq!(joltage(&bank, 0).unwrap().value, 0);
+        assert_eq!(joltage(&bank, 16), None);
+    }
+
+    #[test]
+    fn test_part_1_example() {
+        let input = parse_input(EXAMPLE.as_bytes()).unwrap();
+        let expected = Answers::parse(EXAMPLE_ANSWERS).unwrap();
+        assert_eq!(
+            Some(Day3::part_1(&input, &Default::default())),
+            expected.part_1
+        );
+    }
+
+    #[test]
+    fn test_part_2_example() {
+        let input = parse_input(EXAMPLE.as_bytes()).unwrap();
+        let expected = Answers::parse(EXAMPLE_ANSWERS).unwrap();
+        assert_eq!(
+            Some(Day3::part_2(&input, &Default::default())),
+            expected.part_2
+        );
+    }
+}
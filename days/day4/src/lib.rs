@@ -0,0 +1,167 @@
+use std::io::BufRead;
+
+use aoc_common::{Grid, ParseError, Solution, grid::Position, parse};
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    type Input = Input;
+    type Part1Params = ();
+    type Part2Params = ();
+
+    fn parse(input: impl BufRead) -> Result<Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Input, _: &()) -> u64 {
+        part_1(input)
+    }
+
+    fn part_2(input: &Input, _: &()) -> u64 {
+        part_2(input)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Location {
+    Empty,
+    Roll,
+}
+
+pub type Input = Grid<Location>;
+
+fn parse_input(input: impl BufRead) -> Result<Input, ParseError> {
+    let text = parse::read_to_string(input)?;
+    Grid::parse(&text, |_, c| match c {
+        '@' => Ok(Location::Roll),
+        '.' => Ok(Location::Empty),
+        _ => Err("`@` or `.`"),
+    })
+}
+
+/// A roll with this many neighbouring rolls can't be reached.
+const CROWDED: usize = 4;
+
+fn part_1(input: &Input) -> u64 {
+    find_removable_positions(input).count() as u64
+}
+
+fn find_removable_positions(input: &Input) -> impl Iterator<Item = Position> {
+    input.positions().filter(move |&pos| {
+        input[pos] == Location::Roll
+            && (input
+                .neighbours_8(pos)
+                .filter(|&adjacent| input[adjacent] == Location::Roll)
+                .count()
+                < CROWDED)
+    })
+}
+
+fn part_2(input: &Input) -> u64 {
+    removal_rounds(input)
+        .iter()
+        .map(|&removed| removed as u64)
+        .sum()
+}
+
+/// How many rolls are removed in each round when every reachable roll is
+/// removed at once and the rest are checked again, until none are left to
+/// remove.
+///
+/// Each standing roll keeps a count of its neighbouring rolls, and only the
+/// neighbours of removed rolls are updated, so the whole run takes time
+/// proportional to the size of the grid rather than to it times the number
+/// of rounds.
+pub fn removal_rounds(input: &Input) -> Vec<usize> {
+    // `None` for empty cells and rolls already due for removal.
+    let mut neighbours: Grid<Option<usize>> = input.map(|_| None);
+    for pos in input.positions() {
+        if input[pos] == Location::Roll {
+            let count = input
+                .neighbours_8(pos)
+                .filter(|&adjacent| input[adjacent] == Location::Roll)
+                .count();
+            neighbours[pos] = Some(count);
+        }
+    }
+    let mut round: Vec<Position> = input
+        .positions()
+        .filter(|&pos| neighbours[pos].is_some_and(|count| count < CROWDED))
+        .collect();
+    let mut rounds = Vec::new();
+    while !round.is_empty() {
+        for &pos in &round {
+            neighbours[pos] = None;
+        }
+        let mut next = Vec::new();
+        for &pos in &round {
+            for adjacent in input.neighbours_8(pos) {
+                if let Some(count) = &mut neighbours[adjacent] {
+                    *count -= 1;
+                    if *count < CROWDED {
+                        neighbours[adjacent] = None;
+                        next.push(adjacent);
+                    }
+                }
+            }
+        }
+        rounds.push(round.len());
+        round = next;
+    }
+    rounds
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::Answers;
+
+    use super::*;
+    const EXAMPLE: &str = include_str!("../example.txt");
+    const EXAMPLE_ANSWERS: &str = include_str!("../example_answers.toml");
+
+    #[test]
+    fn test_parse_error_location() {
+        let err = parse_input("..@\n@@\n".as_bytes()).unwrap_err();
+        assert_eq!(err.position(), Some((2, 3)));
+    }
+
+    #[test]
+    fn test_part_1_example() {
+        let input = parse_input(EXAMPLE.as_bytes()).unwrap();
+        let expected = Answers::parse(EXAMPLE_ANSWERS).unwrap();
+        assert_eq!(Some(part_1(&input)), expected.part_1);
+    }
+
+    #[test]
+    fn test_part_2_example() {
+        let input = parse_input(EXAMPLE.as_bytes()).unwrap();
+        let expected = Answers::parse(EXAMPLE_ANSWERS).unwrap();
+        assert_eq!(Some(part_2(&input)), expected.part_2);
+    }
+
+    #[test]
+    fn test_removal_rounds() {
+        let input = parse_input(EXAMPLE.as_bytes()).unwrap();
+        let rounds = removal_rounds(&input);
+        assert_eq!(rounds[0] as u64, part_1(&input));
+        assert!(rounds.iter().all(|&removed| removed > 0));
+
+        // Removing rounds one at a time by rescanning gives the same counts.
+        let mut grid = input.clone();
+        let mut rescanned = Vec::new();
+        loop {
+            let removable: Vec<Position> = find_removable_positions(&grid).collect();
+            if removable.is_empty() {
+                break;
+            }
+            rescanned.push(removable.len());
+            for pos in removable {
+                grid[pos] = Location::Empty;
+            }
+        }
+        assert_eq!(rounds, rescanned);
+        assert!(removal_rounds(&parse_input("...\n".as_bytes()).unwrap()).is_empty());
+    }
+}
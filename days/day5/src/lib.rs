@@ -0,0 +1,109 @@
+use std::{io::BufRead, ops::RangeInclusive};
+
+use aoc_common::{ParseError, Solution, parse, ranges};
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+
+    type Input = Input;
+    type Part1Params = ();
+    type Part2Params = ();
+
+    fn parse(input: impl BufRead) -> Result<Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Input, _: &()) -> u64 {
+        part_1(input)
+    }
+
+    fn part_2(input: &Input, _: &()) -> u64 {
+        part_2(input)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Input {
+    fresh_id_ranges: Vec<RangeInclusive<u64>>,
+    available_ids: Vec<u64>,
+}
+
+fn parse_input(input: impl BufRead) -> Result<Input, ParseError> {
+    let text = parse::read_to_string(input)?;
+    let mut lines = parse::numbered_lines(&text);
+    let mut fresh_id_ranges = Vec::new();
+    loop {
+        let Some((line_number, line)) = lines.next() else {
+            let line_number = text.lines().count() + 1;
+            return Err(ParseError::new(
+                line_number,
+                1,
+                "",
+                "a blank line before the available ids",
+            ));
+        };
+        if line.is_empty() {
+            break;
+        }
+        let Some((low, high)) = line.split_once('-') else {
+            return Err(ParseError::new(line_number, 1, line, "a range like `3-5`"));
+        };
+        let high_column = low.chars().count() + 2;
+        let low = parse::field(line_number, 1, low, "a range start")?;
+        let high = parse::field(line_number, high_column, high, "a range end")?;
+        fresh_id_ranges.push(low..=high);
+    }
+    let available_ids = lines
+        .map(|(line_number, line)| parse::field(line_number, 1, line, "an ingredient id"))
+        .collect::<Result<_, _>>()?;
+    Ok(Input {
+        fresh_id_ranges,
+        available_ids,
+    })
+}
+
+fn part_1(input: &Input) -> u64 {
+    input
+        .available_ids
+        .iter()
+        .filter(|id| input.fresh_id_ranges.iter().any(|range| range.contains(id)))
+        .count() as u64
+}
+
+fn part_2(input: &Input) -> u64 {
+    ranges::merge(&input.fresh_id_ranges)
+        .into_iter()
+        .map(|range| range.end() - range.start() + 1)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::Answers;
+
+    use super::*;
+    const EXAMPLE: &str = include_str!("../example.txt");
+    const EXAMPLE_ANSWERS: &str = include_str!("../example_answers.toml");
+
+    #[test]
+    fn test_parse_error_location() {
+        let err = parse_input("3-5\n10-14\n".as_bytes()).unwrap_err();
+        assert_eq!(err.position(), Some((3, 1)));
+    }
+
+    #[test]
+    fn test_part_1_example() {
+        let input = parse_input(EXAMPLE.as_bytes()).unwrap();
+        let expected = Answers::parse(EXAMPLE_ANSWERS).unwrap();
+        assert_eq!(Some(part_1(&input)), expected.part_1);
+    }
+
+    #[test]
+    fn test_part_2_example() {
+        let input = parse_input(EXAMPLE.as_bytes()).unwrap();
+        let expected = Answers::parse(EXAMPLE_ANSWERS).unwrap();
+        assert_eq!(Some(part_2(&input)), expected.part_2);
+    }
+}
@@ -0,0 +1,319 @@
+use std::{io::BufRead, ops::Range};
+
+use aoc_common::{ParseError, Solution, parse};
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+
+    type Input = Input;
+    type Part1Params = ();
+    type Part2Params = ();
+
+    fn parse(input: impl BufRead) -> Result<Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Input, _: &()) -> u64 {
+        part_1(input)
+    }
+
+    fn part_2(input: &Input, _: &()) -> u64 {
+        part_2(input)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Op {
+    Add,
+    Mul,
+}
+
+impl Op {
+    fn symbol(self) -> &'static str {
+        match self {
+            Op::Add => "+",
+            Op::Mul => "*",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Expression {
+    numbers: Vec<u64>,
+    op: Op,
+}
+
+/// The worksheet's problems read both ways: left to right along each row
+/// for part 1, and down each column for part 2.
+#[derive(Debug, Clone)]
+pub struct Input {
+    by_rows: Vec<Expression>,
+    by_columns: Vec<Expression>,
+}
+
+fn parse_input(input: impl BufRead) -> Result<Input, ParseError> {
+    let text = parse::read_to_string(input)?;
+    let lines: Vec<(usize, &str)> = parse::numbered_lines(&text).collect();
+    let Some(((ops_line_number, ops_line), number_lines)) = lines.split_last() else {
+        return Err(ParseError::new(1, 1, "", "rows of numbers"));
+    };
+    let ops_line_number = *ops_line_number;
+    if number_lines.is_empty() {
+        return Err(ParseError::new(1, 1, *ops_line, "rows of numbers"));
+    }
+    let mut ops = Vec::new();
+    for (column, op) in words(ops_line) {
+        match op {
+            "+" => ops.push((column, Op::Add)),
+            "*" => ops.push((column, Op::Mul)),
+            _ => return Err(ParseError::new(ops_line_number, column, op, "`+` or `*`")),
+        }
+    }
+    let width = number_lines[0].1.chars().count();
+    let mut rows: Vec<Vec<char>> = Vec::with_capacity(number_lines.len());
+    for (line_number, line) in number_lines.iter() {
+        if let Some((i, c)) = line
+            .chars()
+            .enumerate()
+            .find(|(_, c)| !c.is_ascii_digit() && *c != ' ')
+        {
+            return Err(ParseError::new(*line_number, i + 1, c, "a digit or space"));
+        }
+        let length = line.chars().count();
+        if length != width {
+            let found = line.chars().skip(width).collect::<String>();
+            let expected = format!("a row {width} wide");
+            return Err(ParseError::new(
+                *line_number,
+                length.min(width) + 1,
+                found,
+                expected,
+            ));
+        }
+        rows.push(line.chars().collect());
+    }
+
+    let problems = problem_columns(&rows);
+    for (index, columns) in problems.iter().enumerate() {
+        let (start, end) = (columns.start + 1, columns.end);
+        let expected = if start == end {
+            format!("an operation under the problem in column {start}")
+        } else {
+            format!("an operation under the problem in columns {start}-{end}")
+        };
+        match ops.get(index) {
+            Some(&(column, _)) if column < start => {
+                let found = ops_line.chars().nth(column - 1).unwrap_or_default();
+                return Err(ParseError::new(ops_line_number, column, found, expected));
+            }
+            Some(&(column, _)) if column <= end => {}
+            _ => {
+                let found = ops_line.chars().nth(start - 1).filter(|c| *c != ' ');
+                return Err(ParseError::new(
+                    ops_line_number,
+                    start,
+                    found.map(String::from).unwrap_or_default(),
+                    expected,
+                ));
+            }
+        }
+    }
+    if let Some(&(column, op)) = ops.get(problems.len()) {
+        return Err(ParseError::new(
+            ops_line_number,
+            column,
+            op.symbol(),
+            "a problem above each operation",
+        ));
+    }
+
+    let mut by_rows = Vec::with_capacity(problems.len());
+    let mut by_columns = Vec::with_capacity(problems.len());
+    for (columns, &(_, op)) in problems.iter().zip(&ops) {
+        let mut numbers = Vec::with_capacity(rows.len());
+        for ((line_number, _), row) in number_lines.iter().zip(&rows) {
+            let cells: String = row[columns.clone()].iter().collect();
+            let number = cells.trim();
+            let column = columns.start + cells.find(|c| c != ' ').unwrap_or(0) + 1;
+            if number.is_empty() || number.contains(' ') {
+                return Err(ParseError::new(
+                    *line_number,
+                    column,
+                    number,
+                    "one number per problem",
+                ));
+            }
+            numbers.push(parse::field(
+                *line_number,
+                column,
+                number,
+                "a number that fits in 64 bits",
+            )?);
+        }
+        by_rows.push(Expression { numbers, op });
+
+        let mut numbers = Vec::with_capacity(columns.len());
+        for col in columns.clone().rev() {
+            let digits: String = rows
+                .iter()
+                .map(|row| row[col])
+                .filter(|&c| c != ' ')
+                .collect();
+            let line_number =
+                number_lines[rows.iter().position(|row| row[col] != ' ').unwrap_or(0)].0;
+            numbers.push(parse::field(
+                line_number,
+                col + 1,
+                &digits,
+                "a column number that fits in 64 bits",
+            )?);
+        }
+        by_columns.push(Expression { numbers, op });
+    }
+    for expressions in [&by_rows, &by_columns] {
+        for (expression, &(column, op)) in expressions.iter().zip(&ops) {
+            if expression.value().is_none() {
+                return Err(ParseError::new(
+                    ops_line_number,
+                    column,
+                    op.symbol(),
+                    "a problem whose result fits in 64 bits",
+                ));
+            }
+        }
+        if resolve_expressions(expressions).is_none() {
+            return Err(ParseError::new(
+                ops_line_number,
+                1,
+                *ops_line,
+                "problems whose total fits in 64 bits",
+            ));
+        }
+    }
+    Ok(Input {
+        by_rows,
+        by_columns,
+    })
+}
+
+/// Whitespace separated words of `line` with their 1-based columns.
+fn words(line: &str) -> Vec<(usize, &str)> {
+    let mut words = Vec::new();
+    let mut start = None;
+    for (i, c) in line.char_indices().chain([(line.len(), ' ')]) {
+        match (start, c.is_whitespace()) {
+            (None, false) => start = Some(i),
+            (Some(from), true) => {
+                words.push((line[..from].chars().count() + 1, &line[from..i]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    words
+}
+
+/// The 0-based column ranges of each problem: runs of columns with a digit
+/// in some row, split by columns that are blank in every row.
+fn problem_columns(rows: &[Vec<char>]) -> Vec<Range<usize>> {
+    let width = rows.first().map_or(0, Vec::len);
+    let mut problems = Vec::new();
+    let mut start = None;
+    for col in 0..=width {
+        let blank = col == width || rows.iter().all(|row| row[col] == ' ');
+        match (start, blank) {
+            (None, false) => start = Some(col),
+            (Some(from), true) => {
+                problems.push(from..col);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    problems
+}
+
+fn part_1(input: &Input) -> u64 {
+    resolve_expressions(&input.by_rows).expect("checked while parsing")
+}
+
+fn part_2(input: &Input) -> u64 {
+    resolve_expressions(&input.by_columns).expect("checked while parsing")
+}
+
+fn resolve_expressions(expressions: &[Expression]) -> Option<u64> {
+    expressions.iter().try_fold(0u64, |total, expression| {
+        total.checked_add(expression.value()?)
+    })
+}
+
+impl Expression {
+    /// The result, or `None` if it doesn't fit in a `u64`.
+    fn value(&self) -> Option<u64> {
+        match self.op {
+            Op::Add => self
+                .numbers
+                .iter()
+                .try_fold(0u64, |acc, &x| acc.checked_add(x)),
+            Op::Mul => self
+                .numbers
+                .iter()
+                .try_fold(1u64, |acc, &x| acc.checked_mul(x)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::Answers;
+
+    use super::*;
+    const EXAMPLE: &str = include_str!("../example.txt");
+    const EXAMPLE_ANSWERS: &str = include_str!("../example_answers.toml");
+
+    #[test]
+    fn test_parse_error_location() {
+        let err = parse_input("1 2\n3 4\n+ -".as_bytes()).unwrap_err();
+        assert_eq!(err.position(), Some((3, 3)));
+    }
+
+    #[test]
+    fn test_parse_rejects_misaligned_problems() {
+        let err = parse_input("1   2   \n  3    4\n+ *".as_bytes()).unwrap_err();
+        assert_eq!(err.position(), Some((3, 5)));
+        let err = parse_input("12  3\n 4  5\n+  *  +".as_bytes()).unwrap_err();
+        assert_eq!(err.position(), Some((3, 4)));
+        let err = parse_input("1 2\n3 4\n+  ".as_bytes()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 3: expected an operation under the problem in column 3, found end of line"
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_overflow() {
+        let err = parse_input(
+            "123456789012345678901 1\n                    2 3\n*                     +".as_bytes(),
+        )
+        .unwrap_err();
+        assert_eq!(err.position(), Some((1, 1)));
+        let err = parse_input("4294967296 1\n4294967296 2\n*          +".as_bytes()).unwrap_err();
+        assert_eq!(err.position(), Some((3, 1)));
+    }
+
+    #[test]
+    fn test_part_1_example() {
+        let input = parse_input(EXAMPLE.as_bytes()).unwrap();
+        let expected = Answers::parse(EXAMPLE_ANSWERS).unwrap();
+        assert_eq!(Some(part_1(&input)), expected.part_1);
+    }
+
+    #[test]
+    fn test_part_2_example() {
+        let input = parse_input(EXAMPLE.as_bytes()).unwrap();
+        let expected = Answers::parse(EXAMPLE_ANSWERS).unwrap();
+        assert_eq!(Some(part_2(&input)), expected.part_2);
+    }
+}
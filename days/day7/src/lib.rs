@@ -0,0 +1,139 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io::BufRead,
+    mem,
+};
+
+use aoc_common::{Grid, ParseError, Solution, grid::Position, parse};
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+
+    type Input = Input;
+    type Part1Params = ();
+    type Part2Params = ();
+
+    fn parse(input: impl BufRead) -> Result<Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Input, _: &()) -> u64 {
+        part_1(input)
+    }
+
+    fn part_2(input: &Input, _: &()) -> u64 {
+        part_2(input)
+    }
+}
+
+pub enum Location {
+    Space,
+    Splitter,
+}
+
+pub struct Input {
+    start_position: Position,
+    map: Grid<Location>,
+}
+
+fn parse_input(input: impl BufRead) -> Result<Input, ParseError> {
+    let text = parse::read_to_string(input)?;
+    let mut start_position = None;
+    let map = Grid::parse(&text, |position, char| match char {
+        '.' => Ok(Location::Space),
+        '^' => Ok(Location::Splitter),
+        'S' if start_position.is_none() => {
+            start_position = Some(position);
+            Ok(Location::Space)
+        }
+        'S' => Err("a single `S`"),
+        _ => Err("`.`, `^` or `S`"),
+    })?;
+    let Some(start_position) = start_position else {
+        return Err(ParseError::new(map.height(), 1, "", "a start position `S`"));
+    };
+    Ok(Input {
+        start_position,
+        map,
+    })
+}
+
+fn part_1(input: &Input) -> u64 {
+    let mut beams = HashSet::<usize>::from_iter([input.start_position.1]);
+    let mut times_split = 0;
+    for row in 1..input.map.height() {
+        let prev_beams = mem::take(&mut beams);
+        for beam_col in prev_beams.into_iter() {
+            match &input.map[(row, beam_col)] {
+                Location::Space => {
+                    beams.insert(beam_col);
+                }
+                Location::Splitter => {
+                    if let Some(col) = beam_col.checked_sub(1) {
+                        beams.insert(col);
+                    }
+                    beams.insert(beam_col + 1);
+                    times_split += 1
+                }
+            }
+        }
+    }
+    times_split
+}
+
+fn part_2(input: &Input) -> u64 {
+    let mut beams = HashMap::<usize, u64>::from_iter([(input.start_position.1, 1)]);
+    for row in 1..input.map.height() {
+        let prev_beams = mem::take(&mut beams);
+        for (beam_col, paths_to_here) in prev_beams.into_iter() {
+            match &input.map[(row, beam_col)] {
+                Location::Space => {
+                    let entry = beams.entry(beam_col).or_insert(0);
+                    *entry += paths_to_here;
+                }
+                Location::Splitter => {
+                    if let Some(col) = beam_col.checked_sub(1) {
+                        let entry = beams.entry(col).or_insert(0);
+                        *entry += paths_to_here;
+                    }
+                    let entry = beams.entry(beam_col + 1).or_insert(0);
+                    *entry += paths_to_here;
+                }
+            }
+        }
+    }
+    beams.values().sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::Answers;
+
+    use super::*;
+    const EXAMPLE: &str = include_str!("../example.txt");
+    const EXAMPLE_ANSWERS: &str = include_str!("../example_answers.toml");
+
+    #[test]
+    fn test_parse_error_location() {
+        let Err(err) = parse_input("..S..\n..^.S".as_bytes()) else {
+            panic!("expected a parse error");
+        };
+        assert_eq!(err.position(), Some((2, 5)));
+    }
+
+    #[test]
+    fn test_part_1_example() {
+        let input = parse_input(EXAMPLE.as_bytes()).unwrap();
+        let expected = Answers::parse(EXAMPLE_ANSWERS).unwrap();
+        assert_eq!(Some(part_1(&input)), expected.part_1);
+    }
+
+    #[test]
+    fn test_part_2_example() {
+        let input = parse_input(EXAMPLE.as_bytes()).unwrap();
+        let expected = Answers::parse(EXAMPLE_ANSWERS).unwrap();
+        assert_eq!(Some(part_2(&input)), expected.part_2);
+    }
+}
@@ -0,0 +1,164 @@
+//! The single-linkage merge history of the junction boxes: connections are
+//! joined shortest first and every join that merges two clusters is kept,
+//! so questions about any number of joins or any distance can be answered
+//! without recomputing the edges.
+
+use aoc_common::DisjointSetUnion;
+
+use crate::{Point3D, kd_tree::KdTree};
+
+/// A join that merged two clusters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Merge {
+    /// How many connections had been joined, including this one and any
+    /// earlier ones between boxes already in the same cluster.
+    pub joins: usize,
+    /// The two junction boxes, as indices into the input.
+    pub a: usize,
+    pub b: usize,
+    pub distance_squared: u128,
+    /// Size of the cluster this merge produced.
+    pub size: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Dendrogram {
+    points: usize,
+    merges: Vec<Merge>,
+}
+
+impl Dendrogram {
+    /// Joins connections shortest first until every box is in one cluster.
+    pub fn build(points: &[Point3D]) -> Self {
+        let mut merges = Vec::with_capacity(points.len().saturating_sub(1));
+        let mut clusters = DisjointSetUnion::new(points.len());
+        if points.len() > 1 {
+            let tree = KdTree::new(points);
+            for (joins, edge) in (1..).zip(tree.edges()) {
+                let (a, b) = edge.connection;
+                if clusters.union(a, b) {
+                    merges.push(Merge {
+                        joins,
+                        a,
+                        b,
+                        distance_squared: edge.distance,
+                        size: clusters.size(a),
+                    });
+                    if clusters.component_count() == 1 {
+                        break;
+                    }
+                }
+            }
+        }
+        Self {
+            points: points.len(),
+            merges,
+        }
+    }
+
+    /// Every merge, in the order they happened.
+    pub fn merges(&self) -> &[Merge] {
+        &self.merges
+    }
+
+    /// The merge that left a single cluster.
+    pub fn final_merge(&self) -> Option<&Merge> {
+        self.merges.last()
+    }
+
+    /// Cluster sizes, largest first, after joining the `joins` shortest
+    /// connections.
+    pub fn cluster_sizes_after(&self, joins: usize) -> Vec<usize> {
+        let mut sizes: Vec<usize> = self
+            .replay(|merge| merge.joins <= joins)
+            .component_sizes()
+            .collect();
+        sizes.sort_by(|a, b| b.cmp(a));
+        sizes
+    }
+
+    /// The clusters formed by joining every connection no longer than
+    /// `distance_squared`.
+    pub fn clusters_within(&self, distance_squared: u128) -> Vec<Vec<usize>> {
+        self.replay(|merge| merge.distance_squared <= distance_squared)
+            .components()
+            .collect()
+    }
+
+    /// The largest cluster's size after each merge, as `(joins, size)`, for
+    /// plotting how clusters grow.
+    pub fn largest_cluster_curve(&self) -> Vec<(usize, usize)> {
+        let mut largest = 1;
+        self.merges
+            .iter()
+            .map(|merge| {
+                largest = largest.max(merge.size);
+                (merge.joins, largest)
+            })
+            .collect()
+    }
+
+    /// Merges are in increasing join and distance order, so `include`
+    /// selects a prefix of them.
+    fn replay(&self, include: impl Fn(&Merge) -> bool) -> DisjointSetUnion {
+        let mut clusters = DisjointSetUnion::new(self.points);
+        for merge in self.merges.iter().take_while(|merge| include(merge)) {
+            clusters.union(merge.a, merge.b);
+        }
+        clusters
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_input;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn test_example_history() {
+        let input = parse_input(EXAMPLE.as_bytes()).unwrap();
+        let dendrogram = Dendrogram::build(&input);
+        assert_eq!(dendrogram.merges().len(), input.len() - 1);
+        assert_eq!(&dendrogram.cluster_sizes_after(10)[..3], &[5, 4, 2]);
+        assert_eq!(dendrogram.cluster_sizes_after(0).len(), input.len());
+        let last = dendrogram.final_merge().unwrap();
+        assert_eq!(input[last.a].x * input[last.b].x, 25272);
+        assert_eq!(last.size, input.len());
+        assert_eq!(dendrogram.cluster_sizes_after(last.joins - 1).len(), 2);
+        let curve = dendrogram.largest_cluster_curve();
+        assert_eq!(curve.last(), Some(&(last.joins, input.len())));
+        assert!(curve.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+    }
+
+    #[test]
+    fn test_cluster_sizes_match_part_1_with_ties() {
+        let points = crate::kd_tree::tests::lattice();
+        let dendrogram = Dendrogram::build(&points);
+        for joins in 1..300 {
+            let sizes = dendrogram.cluster_sizes_after(joins);
+            let product: u64 = sizes.iter().take(3).map(|&size| size as u64).product();
+            assert_eq!(
+                product,
+                crate::part_1(&points, joins),
+                "after {joins} joins"
+            );
+        }
+    }
+
+    #[test]
+    fn test_clusters_within_distance() {
+        let input = parse_input(EXAMPLE.as_bytes()).unwrap();
+        let dendrogram = Dendrogram::build(&input);
+        let first = &dendrogram.merges()[0];
+        let clusters = dendrogram.clusters_within(first.distance_squared);
+        assert_eq!(clusters.len(), input.len() - 1);
+        assert!(clusters.contains(&{
+            let mut pair = vec![first.a, first.b];
+            pair.sort();
+            pair
+        }));
+        assert_eq!(dendrogram.clusters_within(u128::MAX).len(), 1);
+    }
+}
@@ -0,0 +1,338 @@
+//! A k-d tree over the junction boxes, used to list connections shortest
+//! first without materialising all n² of them.
+
+use std::{cmp::Ordering, collections::BinaryHeap, ops::Range};
+
+use crate::{Point3D, calculate_distance_squared_3d};
+
+const DIMENSIONS: usize = 3;
+
+/// A balanced k-d tree stored implicitly: each subtree is a range of
+/// `order` whose middle element splits the rest on `depth % 3`.
+pub struct KdTree<'a> {
+    points: &'a [Point3D],
+    order: Vec<usize>,
+}
+
+impl<'a> KdTree<'a> {
+    pub fn new(points: &'a [Point3D]) -> Self {
+        let mut order: Vec<usize> = (0..points.len()).collect();
+        build(points, &mut order, 0);
+        Self { points, order }
+    }
+
+    /// The other points ordered by distance from `points[from]`, then by
+    /// index, found by a best-first walk that only opens subtrees as they
+    /// become closest.
+    pub fn neighbours(&self, from: usize) -> Neighbours<'_> {
+        let mut queue = BinaryHeap::new();
+        queue.push(Queued {
+            distance: 0,
+            item: Item::Subtree {
+                range: 0..self.order.len(),
+                depth: 0,
+                bounds: Bounds {
+                    min: [0; DIMENSIONS],
+                    max: [u64::MAX; DIMENSIONS],
+                },
+            },
+        });
+        Neighbours {
+            tree: self,
+            from,
+            queue,
+        }
+    }
+
+    /// Every pair of points as `(a, b)` with `a < b`, ordered by distance and
+    /// then by `(a, b)`. Each point keeps its own [`Neighbours`] walk and
+    /// only the next candidate from each is queued, so memory grows with how
+    /// many edges are taken rather than n².
+    pub fn edges(&self) -> Edges<'_> {
+        let mut neighbours: Vec<Neighbours> = (0..self.points.len())
+            .map(|from| self.neighbours(from))
+            .collect();
+        let heap = neighbours
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(from, walk)| {
+                walk.next().map(|(to, distance)| MinDistanceEdge {
+                    connection: (from, to),
+                    distance,
+                })
+            })
+            .collect();
+        Edges { neighbours, heap }
+    }
+}
+
+fn build(points: &[Point3D], order: &mut [usize], depth: usize) {
+    if order.len() <= 1 {
+        return;
+    }
+    let axis = depth % DIMENSIONS;
+    let mid = order.len() / 2;
+    order.select_nth_unstable_by_key(mid, |&i| points[i].coordinate(axis));
+    let (left, right) = order.split_at_mut(mid);
+    build(points, left, depth + 1);
+    build(points, &mut right[1..], depth + 1);
+}
+
+#[derive(Clone)]
+struct Bounds {
+    min: [u64; DIMENSIONS],
+    max: [u64; DIMENSIONS],
+}
+
+impl Bounds {
+    fn distance_squared(&self, point: &Point3D) -> u128 {
+        (0..DIMENSIONS)
+            .map(|axis| {
+                let c = point.coordinate(axis);
+                let delta = if c < self.min[axis] {
+                    self.min[axis] - c
+                } else {
+                    c.saturating_sub(self.max[axis])
+                };
+                (delta as u128).pow(2)
+            })
+            .sum()
+    }
+}
+
+enum Item {
+    Point(usize),
+    Subtree {
+        range: Range<usize>,
+        depth: usize,
+        bounds: Bounds,
+    },
+}
+
+/// A queue entry; ordered so `BinaryHeap` pops the closest first. At equal
+/// distances subtrees come before points, as they may still hold a point at
+/// that distance, and points come in index order, so ties between
+/// neighbours always resolve the same way.
+struct Queued {
+    distance: u128,
+    item: Item,
+}
+
+impl Queued {
+    fn key(&self) -> (u128, bool, usize) {
+        match self.item {
+            Item::Subtree { .. } => (self.distance, false, 0),
+            Item::Point(index) => (self.distance, true, index),
+        }
+    }
+}
+
+impl PartialEq for Queued {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+impl Eq for Queued {}
+impl PartialOrd for Queued {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for Queued {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.key().cmp(&self.key())
+    }
+}
+
+pub struct Neighbours<'a> {
+    tree: &'a KdTree<'a>,
+    from: usize,
+    queue: BinaryHeap<Queued>,
+}
+
+impl Iterator for Neighbours<'_> {
+    /// A point's index and its squared distance from the start.
+    type Item = (usize, u128);
+
+    fn next(&mut self) -> Option<(usize, u128)> {
+        let points = self.tree.points;
+        let from = &points[self.from];
+        while let Some(Queued { distance, item }) = self.queue.pop() {
+            let (range, depth, bounds) = match item {
+                Item::Point(index) => return Some((index, distance)),
+                Item::Subtree {
+                    range,
+                    depth,
+                    bounds,
+                } => (range, depth, bounds),
+            };
+            let mid = (range.start + range.end) / 2;
+            let index = self.tree.order[mid];
+            if index != self.from {
+                self.queue.push(Queued {
+                    distance: calculate_distance_squared_3d(from, &points[index]),
+                    item: Item::Point(index),
+                });
+            }
+            let axis = depth % DIMENSIONS;
+            let split = points[index].coordinate(axis);
+            let mut left = bounds.clone();
+            left.max[axis] = split;
+            let mut right = bounds;
+            right.min[axis] = split;
+            for (range, bounds) in [(range.start..mid, left), (mid + 1..range.end, right)] {
+                if !range.is_empty() {
+                    self.queue.push(Queued {
+                        distance: bounds.distance_squared(from),
+                        item: Item::Subtree {
+                            range,
+                            depth: depth + 1,
+                            bounds,
+                        },
+                    });
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Ordered so `BinaryHeap` pops the shortest first, with ties broken by the
+/// connection's indices to match [`crate::top_k`].
+pub struct MinDistanceEdge {
+    pub connection: (usize, usize),
+    pub distance: u128,
+}
+
+impl PartialEq for MinDistanceEdge {
+    fn eq(&self, other: &Self) -> bool {
+        (self.distance, self.connection) == (other.distance, other.connection)
+    }
+}
+impl Eq for MinDistanceEdge {}
+impl PartialOrd for MinDistanceEdge {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for MinDistanceEdge {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (other.distance, other.connection).cmp(&(self.distance, self.connection))
+    }
+}
+
+pub struct Edges<'a> {
+    neighbours: Vec<Neighbours<'a>>,
+    heap: BinaryHeap<MinDistanceEdge>,
+}
+
+impl Iterator for Edges<'_> {
+    type Item = MinDistanceEdge;
+
+    fn next(&mut self) -> Option<MinDistanceEdge> {
+        loop {
+            let edge = self.heap.pop()?;
+            let (from, to) = edge.connection;
+            if let Some((next, distance)) = self.neighbours[from].next() {
+                self.heap.push(MinDistanceEdge {
+                    connection: (from, next),
+                    distance,
+                });
+            }
+            // Both ends find every pair; keep the copy found from the
+            // lower index.
+            if from < to {
+                return Some(edge);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) fn scattered_points(count: u64) -> Vec<Point3D> {
+        // A small LCG keeps the test deterministic without a rand dependency.
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = move || {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+            (state >> 33) % 1000
+        };
+        (0..count)
+            .map(|_| Point3D {
+                x: next(),
+                y: next(),
+                z: next(),
+            })
+            .collect()
+    }
+
+    /// A 6×6×3 grid of points 10 apart, listed out of order, so most
+    /// distances are shared by many pairs.
+    pub(crate) fn lattice() -> Vec<Point3D> {
+        let mut points: Vec<Point3D> = (0..6)
+            .flat_map(|x| (0..6).flat_map(move |y| (0..3).map(move |z| (x, y, z))))
+            .map(|(x, y, z)| Point3D {
+                x: 10 * x,
+                y: 10 * y,
+                z: 10 * z,
+            })
+            .collect();
+        points.reverse();
+        points.swap(3, 40);
+        points
+    }
+
+    #[test]
+    fn test_tied_edges_in_index_order() {
+        let points = lattice();
+        let mut expected: Vec<(u128, (usize, usize))> = (0..points.len())
+            .flat_map(|b| (0..b).map(move |a| (a, b)))
+            .map(|(a, b)| {
+                (
+                    calculate_distance_squared_3d(&points[a], &points[b]),
+                    (a, b),
+                )
+            })
+            .collect();
+        expected.sort();
+        let found: Vec<(u128, (usize, usize))> = KdTree::new(&points)
+            .edges()
+            .map(|edge| (edge.distance, edge.connection))
+            .collect();
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn test_edges_match_all_pairs_by_distance() {
+        let points = scattered_points(200);
+        let mut expected: Vec<u128> = (0..points.len())
+            .flat_map(|a| (0..a).map(move |b| (a, b)))
+            .map(|(a, b)| calculate_distance_squared_3d(&points[a], &points[b]))
+            .collect();
+        expected.sort();
+        let tree = KdTree::new(&points);
+        let found: Vec<MinDistanceEdge> = tree.edges().collect();
+        assert_eq!(found.len(), expected.len());
+        for (edge, expected) in found.iter().zip(expected) {
+            let (a, b) = edge.connection;
+            assert!(a < b);
+            assert_eq!(edge.distance, expected);
+            assert_eq!(
+                calculate_distance_squared_3d(&points[a], &points[b]),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_neighbours_skip_the_start_point() {
+        let points = scattered_points(50);
+        let tree = KdTree::new(&points);
+        let neighbours: Vec<(usize, u128)> = tree.neighbours(7).collect();
+        assert_eq!(neighbours.len(), points.len() - 1);
+        assert!(neighbours.iter().all(|&(index, _)| index != 7));
+        assert!(neighbours.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+    }
+}
@@ -0,0 +1,136 @@
+use std::{io::BufRead, num::NonZero, thread};
+
+use aoc_common::{DisjointSetUnion, ParseError, Solution, parse};
+pub use dendrogram::{Dendrogram, Merge};
+
+mod dendrogram;
+mod kd_tree;
+mod top_k;
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+
+    type Input = Input;
+    type Part1Params = Part1Params;
+    type Part2Params = ();
+
+    fn parse(input: impl BufRead) -> Result<Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Input, params: &Part1Params) -> u64 {
+        part_1(input, params.joins)
+    }
+
+    fn part_2(input: &Input, _: &()) -> u64 {
+        part_2(input)
+    }
+
+    fn part_1_example_params() -> Part1Params {
+        Part1Params { joins: 10 }
+    }
+}
+
+pub struct Part1Params {
+    /// How many of the shortest connections to join before measuring clusters.
+    pub joins: usize,
+}
+
+impl Default for Part1Params {
+    fn default() -> Self {
+        Self { joins: 1000 }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Point3D {
+    x: u64,
+    y: u64,
+    z: u64,
+}
+
+impl Point3D {
+    fn coordinate(&self, axis: usize) -> u64 {
+        match axis {
+            0 => self.x,
+            1 => self.y,
+            _ => self.z,
+        }
+    }
+}
+
+pub type Input = Vec<Point3D>;
+
+fn parse_input(input: impl BufRead) -> Result<Input, ParseError> {
+    let text = parse::read_to_string(input)?;
+    parse::numbered_lines(&text)
+        .map(|(line_number, line)| {
+            let mut split = parse::fields(line, ',');
+            let x = parse::next_field(&mut split, line_number, line, "an x coordinate")?;
+            let y = parse::next_field(&mut split, line_number, line, "a y coordinate")?;
+            let z = parse::next_field(&mut split, line_number, line, "a z coordinate")?;
+            parse::end_of_fields(&mut split, line_number)?;
+            Ok(Point3D { x, y, z })
+        })
+        .collect()
+}
+
+fn part_1(input: &Input, n: usize) -> u64 {
+    let threads = thread::available_parallelism().map_or(1, NonZero::get);
+    let mut clusters = DisjointSetUnion::new(input.len());
+    for (a, b) in top_k::shortest_connections(input, n, threads) {
+        clusters.union(a, b);
+    }
+    let mut sizes: Vec<usize> = clusters.component_sizes().collect();
+    sizes.sort_by(|a, b| b.cmp(a));
+    sizes.iter().take(3).map(|&size| size as u64).product()
+}
+
+fn calculate_distance_squared_3d(a: &Point3D, b: &Point3D) -> u128 {
+    let x1 = a.x as i64;
+    let x2 = b.x as i64;
+    let y1 = a.y as i64;
+    let y2 = b.y as i64;
+    let z1 = a.z as i64;
+    let z2 = b.z as i64;
+    ((x2 - x1).abs().pow(2) + (y2 - y1).abs().pow(2) + (z2 - z1).abs().pow(2)) as u128
+}
+
+fn part_2(input: &Input) -> u64 {
+    let dendrogram = Dendrogram::build(input);
+    let last = dendrogram
+        .final_merge()
+        .expect("at least two junction boxes");
+    input[last.a].x * input[last.b].x
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::Answers;
+
+    use super::*;
+    const EXAMPLE: &str = include_str!("../example.txt");
+    const EXAMPLE_ANSWERS: &str = include_str!("../example_answers.toml");
+
+    #[test]
+    fn test_parse_error_location() {
+        let err = parse_input("162,817,812\n57,618".as_bytes()).unwrap_err();
+        assert_eq!(err.position(), Some((2, 7)));
+    }
+
+    #[test]
+    fn test_part_1_example() {
+        let input = parse_input(EXAMPLE.as_bytes()).unwrap();
+        let expected = Answers::parse(EXAMPLE_ANSWERS).unwrap();
+        assert_eq!(Some(part_1(&input, 10)), expected.part_1);
+    }
+
+    #[test]
+    fn test_part_2_example() {
+        let input = parse_input(EXAMPLE.as_bytes()).unwrap();
+        let expected = Answers::parse(EXAMPLE_ANSWERS).unwrap();
+        assert_eq!(Some(part_2(&input)), expected.part_2);
+    }
+}
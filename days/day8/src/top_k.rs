@@ -0,0 +1,75 @@
+//! Selecting the k shortest connections by scanning every pair with a
+//! bounded heap, so memory stays O(n + k) however many pairs there are.
+
+use std::{collections::BinaryHeap, thread};
+
+use crate::{Point3D, calculate_distance_squared_3d};
+
+/// `(distance², a, b)` with `a < b`. Ties on distance fall back to the
+/// indices so the selection doesn't depend on how the scan was split up.
+type Candidate = (u128, usize, usize);
+
+/// The `k` shortest connections, shortest first, as `(a, b)` index pairs.
+/// Rows of the pair triangle are dealt round-robin to `threads` scoped
+/// threads, each keeping its own best `k`, which are merged at the end.
+pub fn shortest_connections(points: &[Point3D], k: usize, threads: usize) -> Vec<(usize, usize)> {
+    let threads = threads.max(1);
+    let mut best = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|first_row| {
+                scope.spawn(move || {
+                    let mut best = BinaryHeap::with_capacity(k + 1);
+                    for b in (first_row..points.len()).step_by(threads) {
+                        for a in 0..b {
+                            let distance = calculate_distance_squared_3d(&points[a], &points[b]);
+                            keep(&mut best, k, (distance, a, b));
+                        }
+                    }
+                    best
+                })
+            })
+            .collect();
+        let mut best = BinaryHeap::with_capacity(k + 1);
+        for worker in workers {
+            for candidate in worker.join().expect("edge scan panicked") {
+                keep(&mut best, k, candidate);
+            }
+        }
+        best
+    })
+    .into_vec();
+    best.sort_unstable();
+    best.into_iter().map(|(_, a, b)| (a, b)).collect()
+}
+
+/// Adds `candidate` to the max-heap `best` if it is among the `k` smallest.
+fn keep(best: &mut BinaryHeap<Candidate>, k: usize, candidate: Candidate) {
+    if best.len() < k {
+        best.push(candidate);
+    } else if let Some(mut worst) = best.peek_mut()
+        && candidate < *worst
+    {
+        *worst = candidate;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kd_tree::tests::scattered_points;
+
+    #[test]
+    fn test_matches_full_sort() {
+        let points = scattered_points(300);
+        let mut all: Vec<Candidate> = (0..points.len())
+            .flat_map(|b| (0..b).map(move |a| (a, b)))
+            .map(|(a, b)| (calculate_distance_squared_3d(&points[a], &points[b]), a, b))
+            .collect();
+        all.sort_unstable();
+        let expected: Vec<(usize, usize)> = all.iter().take(500).map(|&(_, a, b)| (a, b)).collect();
+        assert_eq!(shortest_connections(&points, 500, 1), expected);
+        assert_eq!(shortest_connections(&points, 500, 7), expected);
+        assert!(shortest_connections(&points, 0, 4).is_empty());
+        assert_eq!(shortest_connections(&points[..3], 10, 2).len(), 3);
+    }
+}
@@ -0,0 +1,433 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BinaryHeap, HashMap},
+    io::BufRead,
+};
+
+use aoc_common::{Grid, ParseError, Solution, grid::Position, parse};
+pub use polygon::{PolygonError, RectilinearPolygon};
+pub use svg::svg;
+
+mod polygon;
+mod svg;
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+
+    type Input = Input;
+    type Part1Params = ();
+    type Part2Params = ();
+
+    fn parse(input: impl BufRead) -> Result<Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Input, _: &()) -> u64 {
+        part_1(input)
+    }
+
+    fn part_2(input: &Input, _: &()) -> u64 {
+        part_2(input)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Point2D {
+    pub x: usize,
+    pub y: usize,
+}
+
+pub type Input = RectilinearPolygon;
+
+/// The largest coordinate for which the area between any two tiles still
+/// fits in a `u64`.
+const MAX_COORDINATE: usize = u32::MAX as usize - 1;
+
+fn parse_input(input: impl BufRead) -> Result<Input, ParseError> {
+    let text = parse::read_to_string(input)?;
+    let points = parse::numbered_lines(&text)
+        .map(|(line_number, line)| {
+            let mut split = parse::fields(line, ',');
+            let x = parse::next_field(&mut split, line_number, line, "an x coordinate")?;
+            let y = parse::next_field(&mut split, line_number, line, "a y coordinate")?;
+            parse::end_of_fields(&mut split, line_number)?;
+            let (x_text, y_text) = line.split_once(',').unwrap_or((line, ""));
+            if x > MAX_COORDINATE {
+                return Err(ParseError::new(
+                    line_number,
+                    1,
+                    x_text,
+                    "an x coordinate below 4294967295",
+                ));
+            }
+            if y > MAX_COORDINATE {
+                return Err(ParseError::new(
+                    line_number,
+                    x_text.chars().count() + 2,
+                    y_text,
+                    "a y coordinate below 4294967295",
+                ));
+            }
+            Ok(Point2D { x, y })
+        })
+        .collect::<Result<Vec<_>, ParseError>>()?;
+    RectilinearPolygon::new(points).map_err(|err| {
+        let line_number = err.vertex() + 1;
+        let line = text.lines().nth(err.vertex()).unwrap_or("");
+        ParseError::new(
+            line_number,
+            1,
+            line,
+            format!("a simple rectilinear loop ({err})"),
+        )
+    })
+}
+
+/// A rectangle given by two opposite corners.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rect {
+    pub a: Point2D,
+    pub b: Point2D,
+}
+
+/// A valid part 2 rectangle and where it places among all of them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RankedRect {
+    /// Corners on red tiles, in the input's coordinates.
+    pub rect: Rect,
+    /// Tiles covered, counting both edges.
+    pub area: u64,
+    /// 1 for the largest; rectangles with equal areas share a rank and the
+    /// next area's rank skips past them.
+    pub rank: usize,
+    /// How many valid rectangles have this area, including this one.
+    pub tied: usize,
+}
+
+fn part_1(input: &Input) -> u64 {
+    let input = input.vertices();
+    (0..input.len())
+        .flat_map(|a| {
+            (0..a).map(move |b| Rect {
+                a: input[a].clone(),
+                b: input[b].clone(),
+            })
+        })
+        .map(|rect| area_between_points(&rect.a, &rect.b))
+        .max()
+        .unwrap()
+}
+
+fn part_2(input: &Input) -> u64 {
+    ValidRects::new(input)
+        .iter()
+        .map(|(area, _)| area)
+        .max()
+        .expect("neighbouring red tiles always make a valid rectangle")
+}
+
+/// The part 2 answer with its geometry.
+pub fn largest_rect(polygon: &RectilinearPolygon) -> Option<RankedRect> {
+    largest_rects(polygon, 1).pop()
+}
+
+/// The `k` largest rectangles with red tiles at opposite corners that lie
+/// inside the loop, largest first. Equal areas keep input order.
+///
+/// Only the best `k` so far are kept, in a heap whose top is the one to drop
+/// next, along with how many rectangles have each area at least as large as
+/// that one's, which is all the ranks and ties need.
+pub fn largest_rects(polygon: &RectilinearPolygon, k: usize) -> Vec<RankedRect> {
+    if k == 0 {
+        return Vec::new();
+    }
+    // `(Reverse(area), order found)`, so the heap's maximum is the smallest
+    // area, found last among equals.
+    let mut best: BinaryHeap<(Reverse<u64>, usize, (usize, usize))> =
+        BinaryHeap::with_capacity(k + 1);
+    let mut counts: BTreeMap<u64, usize> = BTreeMap::new();
+    for (order, (area, corners)) in ValidRects::new(polygon).iter().enumerate() {
+        let entry = (Reverse(area), order, corners);
+        if best.len() == k {
+            let mut worst = best.peek_mut().expect("the heap holds k > 0 rectangles");
+            if entry.0 > worst.0 {
+                continue;
+            }
+            *counts.entry(area).or_default() += 1;
+            if entry < *worst {
+                *worst = entry;
+            }
+        } else {
+            *counts.entry(area).or_default() += 1;
+            best.push(entry);
+        }
+        if best.len() == k
+            && let Some((Reverse(threshold), ..)) = best.peek()
+        {
+            counts = counts.split_off(threshold);
+        }
+    }
+
+    let vertices = polygon.vertices();
+    let mut larger = 0;
+    let mut ranks = HashMap::new();
+    for (&area, &tied) in counts.iter().rev() {
+        ranks.insert(area, (larger + 1, tied));
+        larger += tied;
+    }
+    best.into_sorted_vec()
+        .into_iter()
+        .map(|(Reverse(area), _, (a, b))| {
+            let (rank, tied) = ranks[&area];
+            RankedRect {
+                rect: Rect {
+                    a: vertices[a].clone(),
+                    b: vertices[b].clone(),
+                },
+                area,
+                rank,
+                tied,
+            }
+        })
+        .collect()
+}
+
+/// Finds the rectangles with red tiles at opposite corners that lie inside
+/// the loop, working on a compressed grid. The `i`th distinct coordinate
+/// maps to grid index `2i + 1`, so the tiles between two neighbouring
+/// coordinates get a cell of their own; without it a notch only one step
+/// wide would vanish. Index 0 and the last index are a border of outside
+/// cells for the flood fill to start from.
+struct ValidRects<'a> {
+    vertices: &'a [Point2D],
+    compressed: Vec<Point2D>,
+    outside: PrefixSums,
+}
+
+impl<'a> ValidRects<'a> {
+    fn new(polygon: &'a RectilinearPolygon) -> Self {
+        let input = polygon.vertices();
+        let distinct = |coordinate: fn(&Point2D) -> usize| {
+            let mut values: Vec<usize> = input.iter().map(coordinate).collect();
+            values.sort_unstable();
+            values.dedup();
+            values
+        };
+        let xs = distinct(|point| point.x);
+        let ys = distinct(|point| point.y);
+        let index = |values: &[usize], value: usize| {
+            2 * values
+                .binary_search(&value)
+                .expect("every vertex was added")
+                + 1
+        };
+        let compress = |point: &Point2D| Point2D {
+            x: index(&xs, point.x),
+            y: index(&ys, point.y),
+        };
+
+        let mut grid = Grid::new(2 * ys.len() + 1, 2 * xs.len() + 1, false);
+        let draw_edges = |grid: &mut Grid<bool>, value: bool| {
+            for (a, b) in polygon.edges() {
+                let (a, b) = (compress(a), compress(b));
+                for row in a.y.min(b.y)..=a.y.max(b.y) {
+                    for col in a.x.min(b.x)..=a.x.max(b.x) {
+                        grid[(row, col)] = value;
+                    }
+                }
+            }
+        };
+        draw_edges(&mut grid, true);
+        grid.flood_fill((0, 0), true);
+        draw_edges(&mut grid, false);
+        Self {
+            vertices: input,
+            compressed: input.iter().map(compress).collect(),
+            outside: PrefixSums::new(&grid),
+        }
+    }
+
+    /// Each valid rectangle's area with the indices of its corner vertices,
+    /// in a fixed order.
+    fn iter(&self) -> impl Iterator<Item = (u64, (usize, usize))> + '_ {
+        let points = &self.compressed;
+        (0..points.len())
+            .flat_map(|a| (0..a).map(move |b| (a, b)))
+            .filter(|&(a, b)| {
+                let (a, b) = (&points[a], &points[b]);
+                let (top, bottom) = (a.y.min(b.y), a.y.max(b.y));
+                let (left, right) = (a.x.min(b.x), a.x.max(b.x));
+                self.outside.count((top, left), (bottom, right)) == 0
+            })
+            .map(|(a, b)| {
+                let area = area_between_points(&self.vertices[a], &self.vertices[b]);
+                (area, (a, b))
+            })
+    }
+}
+
+/// Counts of `true` cells over any rectangle of a grid in constant time.
+/// `sums[(r, c)]` holds the count over rows `..r` and columns `..c`.
+struct PrefixSums {
+    sums: Grid<usize>,
+}
+
+impl PrefixSums {
+    fn new(grid: &Grid<bool>) -> Self {
+        let mut sums = Grid::new(grid.height() + 1, grid.width() + 1, 0);
+        for (row, col) in grid.positions() {
+            sums[(row + 1, col + 1)] =
+                usize::from(grid[(row, col)]) + sums[(row, col + 1)] + sums[(row + 1, col)]
+                    - sums[(row, col)];
+        }
+        Self { sums }
+    }
+
+    /// `true` cells between the inclusive corners `top_left` and
+    /// `bottom_right`.
+    fn count(&self, (top, left): Position, (bottom, right): Position) -> usize {
+        let sums = &self.sums;
+        sums[(bottom + 1, right + 1)] + sums[(top, left)]
+            - sums[(top, right + 1)]
+            - sums[(bottom + 1, left)]
+    }
+}
+
+fn area_between_points(a: &Point2D, b: &Point2D) -> u64 {
+    let x1 = a.x as i64;
+    let x2 = b.x as i64;
+    let y1 = a.y as i64;
+    let y2 = b.y as i64;
+    ((x2 - x1).unsigned_abs() + 1) * ((y2 - y1).unsigned_abs() + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::Answers;
+
+    use super::*;
+    const EXAMPLE: &str = include_str!("../example.txt");
+    const EXAMPLE_ANSWERS: &str = include_str!("../example_answers.toml");
+
+    #[test]
+    fn test_parse_error_location() {
+        let err = parse_input("7,1\n11,1,3".as_bytes()).unwrap_err();
+        assert_eq!(err.position(), Some((2, 6)));
+    }
+
+    #[test]
+    fn test_parse_rejects_coordinates_too_large_for_areas() {
+        let err = parse_input("0,0\n4294967295,0\n4294967295,1\n0,1".as_bytes()).unwrap_err();
+        assert_eq!(err.position(), Some((2, 1)));
+        let err = parse_input("0,0\n1,0\n1,18446744073709551615\n0,1".as_bytes()).unwrap_err();
+        assert_eq!(err.position(), Some((3, 3)));
+
+        let max = MAX_COORDINATE;
+        let input = parse_input(format!("0,0\n{max},0\n{max},{max}\n0,{max}").as_bytes()).unwrap();
+        assert_eq!(part_1(&input), (max as u64 + 1).pow(2));
+        assert_eq!(part_2(&input), (max as u64 + 1).pow(2));
+    }
+
+    #[test]
+    fn test_parse_rejects_diagonal_edge() {
+        let err = parse_input("0,0\n2,0\n3,2\n0,2".as_bytes()).unwrap_err();
+        assert_eq!(err.position(), Some((2, 1)));
+    }
+
+    #[test]
+    fn test_largest_rects_example() {
+        let input = parse_input(EXAMPLE.as_bytes()).unwrap();
+        let expected = Answers::parse(EXAMPLE_ANSWERS).unwrap();
+        let best = largest_rect(&input).unwrap();
+        assert_eq!(Some(best.area), expected.part_2);
+        assert_eq!((best.rank, best.tied), (1, 1));
+        assert_eq!(area_between_points(&best.rect.a, &best.rect.b), best.area);
+        assert!(input.contains_rect(&best.rect.a, &best.rect.b));
+
+        let top = largest_rects(&input, 5);
+        assert_eq!(top.len(), 5);
+        assert_eq!(top[0], best);
+        for pair in top.windows(2) {
+            assert!(pair[0].area >= pair[1].area);
+            if pair[0].area == pair[1].area {
+                assert_eq!(pair[0].rank, pair[1].rank);
+                assert_eq!(pair[0].tied, pair[1].tied);
+            } else {
+                assert!(pair[1].rank > pair[0].rank);
+            }
+        }
+    }
+
+    #[test]
+    fn test_largest_rect_skips_narrow_notch() {
+        // A U whose gap is a single compressed step wide: x 2 to 6 above y 6.
+        let input = parse_input("0,0\n2,0\n2,6\n6,6\n6,0\n8,0\n8,8\n0,8".as_bytes()).unwrap();
+        let best = largest_rect(&input).unwrap();
+        assert!(input.contains_rect(&best.rect.a, &best.rect.b));
+        assert_eq!(best.area, 9 * 3);
+        assert_eq!(part_2(&input), 9 * 3);
+
+        for polygon in [input, parse_input(EXAMPLE.as_bytes()).unwrap()] {
+            let vertices = polygon.vertices();
+            let valid: Vec<(usize, usize)> = ValidRects::new(&polygon)
+                .iter()
+                .map(|(_, corners)| corners)
+                .collect();
+            let expected: Vec<(usize, usize)> = (0..vertices.len())
+                .flat_map(|a| (0..a).map(move |b| (a, b)))
+                .filter(|&(a, b)| polygon.contains_rect(&vertices[a], &vertices[b]))
+                .collect();
+            assert_eq!(valid, expected);
+        }
+    }
+
+    #[test]
+    fn test_largest_rects_match_full_ranking() {
+        let input = parse_input(EXAMPLE.as_bytes()).unwrap();
+        let mut all: Vec<(u64, (usize, usize))> = ValidRects::new(&input).iter().collect();
+        all.sort_by_key(|&(area, _)| Reverse(area));
+        let ranked: Vec<(u64, usize, usize)> = all
+            .iter()
+            .map(|&(area, _)| {
+                let larger = all.iter().filter(|other| other.0 > area).count();
+                let tied = all.iter().filter(|other| other.0 == area).count();
+                (area, larger + 1, tied)
+            })
+            .collect();
+        for k in [0, 1, 3, 10, all.len(), all.len() + 5] {
+            let top = largest_rects(&input, k);
+            assert_eq!(top.len(), k.min(all.len()));
+            for ((found, &(area, rank, tied)), &(_, (a, b))) in top.iter().zip(&ranked).zip(&all) {
+                assert_eq!((found.area, found.rank, found.tied), (area, rank, tied));
+                assert_eq!(found.rect.a, input.vertices()[a]);
+                assert_eq!(found.rect.b, input.vertices()[b]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_prefix_sums_count() {
+        let grid = Grid::parse("#..#\n.##.\n#...", |_, c| Ok::<_, &str>(c == '#')).unwrap();
+        let sums = PrefixSums::new(&grid);
+        assert_eq!(sums.count((0, 0), (2, 3)), 5);
+        assert_eq!(sums.count((1, 1), (1, 2)), 2);
+        assert_eq!(sums.count((0, 1), (0, 2)), 0);
+        assert_eq!(sums.count((2, 0), (2, 0)), 1);
+    }
+
+    #[test]
+    fn test_part_1_example() {
+        let input = parse_input(EXAMPLE.as_bytes()).unwrap();
+        let expected = Answers::parse(EXAMPLE_ANSWERS).unwrap();
+        assert_eq!(Some(part_1(&input)), expected.part_1);
+    }
+
+    #[test]
+    fn test_part_2_example() {
+        let input = parse_input(EXAMPLE.as_bytes()).unwrap();
+        let expected = Answers::parse(EXAMPLE_ANSWERS).unwrap();
+        assert_eq!(Some(part_2(&input)), expected.part_2);
+    }
+}
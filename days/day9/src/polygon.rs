@@ -0,0 +1,328 @@
+//! The red tiles as a closed rectilinear polygon: each tile is joined to the
+//! next, and the last back to the first, by a horizontal or vertical line.
+//! The polygon is the closed region, so tiles on the boundary count as
+//! inside.
+
+use std::{
+    error::Error,
+    fmt::{self, Display},
+};
+
+use crate::Point2D;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RectilinearPolygon {
+    vertices: Vec<Point2D>,
+}
+
+/// Why a list of tiles isn't a simple rectilinear polygon. Vertices are
+/// counted from 0 in input order; messages count from 1 to match line
+/// numbers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolygonError {
+    TooFewVertices(usize),
+    /// The same tile twice in a row, giving an edge of length 0.
+    RepeatedVertex(usize),
+    /// The edge from this vertex to the next is diagonal.
+    NotAxisAligned(usize),
+    /// The edge from the last vertex back to the first is diagonal.
+    NotClosed,
+    /// Both edges at this vertex run along the same line, so it isn't a
+    /// corner.
+    Collinear(usize),
+    /// The edges starting at these two vertices touch or cross.
+    SelfIntersection(usize, usize),
+}
+
+impl PolygonError {
+    /// The vertex the problem was found at.
+    pub fn vertex(&self) -> usize {
+        match *self {
+            PolygonError::TooFewVertices(count) => count.saturating_sub(1),
+            PolygonError::NotClosed => 0,
+            PolygonError::RepeatedVertex(vertex)
+            | PolygonError::NotAxisAligned(vertex)
+            | PolygonError::Collinear(vertex)
+            | PolygonError::SelfIntersection(_, vertex) => vertex,
+        }
+    }
+}
+
+impl Display for PolygonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolygonError::TooFewVertices(count) => {
+                write!(f, "a polygon needs at least 4 vertices, found {count}")
+            }
+            PolygonError::RepeatedVertex(vertex) => {
+                write!(f, "vertex {} repeats the one before it", vertex + 1)
+            }
+            PolygonError::NotAxisAligned(vertex) => write!(
+                f,
+                "the edge from vertex {} to vertex {} is not horizontal or vertical",
+                vertex + 1,
+                vertex + 2
+            ),
+            PolygonError::NotClosed => write!(
+                f,
+                "the edge from the last vertex back to the first is not horizontal or vertical"
+            ),
+            PolygonError::Collinear(vertex) => {
+                write!(
+                    f,
+                    "vertex {} is in line with both its neighbours",
+                    vertex + 1
+                )
+            }
+            PolygonError::SelfIntersection(a, b) => write!(
+                f,
+                "the edges from vertex {} and vertex {} intersect",
+                a + 1,
+                b + 1
+            ),
+        }
+    }
+}
+
+impl Error for PolygonError {}
+
+impl RectilinearPolygon {
+    pub fn new(vertices: Vec<Point2D>) -> Result<Self, PolygonError> {
+        let n = vertices.len();
+        if n < 4 {
+            return Err(PolygonError::TooFewVertices(n));
+        }
+        let polygon = Self { vertices };
+        for (i, (a, b)) in polygon.edges().enumerate() {
+            if a == b {
+                return Err(PolygonError::RepeatedVertex((i + 1) % n));
+            }
+            if a.x != b.x && a.y != b.y {
+                return Err(if i == n - 1 {
+                    PolygonError::NotClosed
+                } else {
+                    PolygonError::NotAxisAligned(i)
+                });
+            }
+        }
+        for (i, (a, b)) in polygon.edges().enumerate() {
+            let c = &polygon.vertices[(i + 2) % n];
+            if (a.x == b.x) == (b.x == c.x) {
+                return Err(PolygonError::Collinear((i + 1) % n));
+            }
+        }
+        // Edges next to each other meet at their shared corner and nowhere
+        // else once the checks above pass, so only the rest need comparing.
+        let edges: Vec<_> = polygon.edges().collect();
+        for i in 0..n {
+            for j in i + 2..n {
+                if i == 0 && j == n - 1 {
+                    continue;
+                }
+                if touches(edges[i], edges[j]) {
+                    return Err(PolygonError::SelfIntersection(i, j));
+                }
+            }
+        }
+        Ok(polygon)
+    }
+
+    pub fn vertices(&self) -> &[Point2D] {
+        &self.vertices
+    }
+
+    /// Each vertex paired with the next, ending with the closing edge.
+    pub fn edges(&self) -> impl Iterator<Item = (&Point2D, &Point2D)> {
+        self.vertices
+            .iter()
+            .zip(self.vertices.iter().cycle().skip(1))
+    }
+
+    pub fn contains_point(&self, point: &Point2D) -> bool {
+        self.contains_doubled(double(point))
+    }
+
+    /// Whether the horizontal or vertical segment from `a` to `b` lies
+    /// entirely inside.
+    ///
+    /// # Panics
+    ///
+    /// If the segment is diagonal.
+    pub fn contains_segment(&self, a: &Point2D, b: &Point2D) -> bool {
+        assert!(
+            a.x == b.x || a.y == b.y,
+            "segment {a:?} to {b:?} is not horizontal or vertical"
+        );
+        let (a, b) = (double(a), double(b));
+        // Being inside can only change where a vertex lines up with the
+        // segment, so checking those points and one point between each
+        // consecutive pair covers the whole segment. Doubled coordinates
+        // keep the midpoints integral.
+        let along = |point: (i64, i64)| if a.1 == b.1 { point.0 } else { point.1 };
+        let at = |t: i64| if a.1 == b.1 { (t, a.1) } else { (a.0, t) };
+        let (from, to) = (along(a).min(along(b)), along(a).max(along(b)));
+        let mut stops: Vec<i64> = self
+            .vertices
+            .iter()
+            .map(|vertex| along(double(vertex)))
+            .filter(|&t| from < t && t < to)
+            .chain([from, to])
+            .collect();
+        stops.sort_unstable();
+        stops.dedup();
+        stops.iter().all(|&t| self.contains_doubled(at(t)))
+            && stops
+                .windows(2)
+                .all(|pair| self.contains_doubled(at((pair[0] + pair[1]) / 2)))
+    }
+
+    /// Whether the axis-aligned rectangle with opposite corners `a` and `b`
+    /// lies entirely inside.
+    pub fn contains_rect(&self, a: &Point2D, b: &Point2D) -> bool {
+        let (left, right) = (a.x.min(b.x), a.x.max(b.x));
+        let (top, bottom) = (a.y.min(b.y), a.y.max(b.y));
+        if left == right || top == bottom {
+            return self.contains_segment(a, &Point2D { x: b.x, y: a.y })
+                && self.contains_segment(a, &Point2D { x: a.x, y: b.y });
+        }
+        // With no edge passing through the open rectangle, it is all inside
+        // or all outside, and its centre says which.
+        let crossed = self.edges().any(|(p, q)| {
+            left < p.x.max(q.x)
+                && p.x.min(q.x) < right
+                && top < p.y.max(q.y)
+                && p.y.min(q.y) < bottom
+        });
+        let centre = ((left + right) as i64, (top + bottom) as i64);
+        !crossed && self.contains_doubled(centre)
+    }
+
+    /// Point in polygon for a point with doubled coordinates: on an edge, or
+    /// crossing an odd number of vertical edges on the way to +x.
+    fn contains_doubled(&self, (x, y): (i64, i64)) -> bool {
+        let mut crossings = 0;
+        for (a, b) in self.edges() {
+            let (a, b) = (double(a), double(b));
+            let (min_x, max_x) = (a.0.min(b.0), a.0.max(b.0));
+            let (min_y, max_y) = (a.1.min(b.1), a.1.max(b.1));
+            if min_x <= x && x <= max_x && min_y <= y && y <= max_y {
+                return true;
+            }
+            if a.0 == b.0 && a.0 > x && min_y <= y && y < max_y {
+                crossings += 1;
+            }
+        }
+        crossings % 2 == 1
+    }
+}
+
+fn double(point: &Point2D) -> (i64, i64) {
+    (2 * point.x as i64, 2 * point.y as i64)
+}
+
+/// Whether two axis-aligned segments share any point; each is its own
+/// bounding box, so overlapping boxes is enough.
+fn touches((a, b): (&Point2D, &Point2D), (c, d): (&Point2D, &Point2D)) -> bool {
+    a.x.min(b.x).max(c.x.min(d.x)) <= a.x.max(b.x).min(c.x.max(d.x))
+        && a.y.min(b.y).max(c.y.min(d.y)) <= a.y.max(b.y).min(c.y.max(d.y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(coordinates: &[(usize, usize)]) -> Vec<Point2D> {
+        coordinates.iter().map(|&(x, y)| Point2D { x, y }).collect()
+    }
+
+    /// A U opening upwards: arms at x 0..=2 and 6..=8, base y 6..=8.
+    fn u_shape() -> RectilinearPolygon {
+        RectilinearPolygon::new(points(&[
+            (0, 0),
+            (2, 0),
+            (2, 6),
+            (6, 6),
+            (6, 0),
+            (8, 0),
+            (8, 8),
+            (0, 8),
+        ]))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_rejects_invalid_loops() {
+        let new = |coordinates: &[(usize, usize)]| RectilinearPolygon::new(points(coordinates));
+        assert_eq!(
+            new(&[(0, 0), (2, 0), (2, 2)]),
+            Err(PolygonError::TooFewVertices(3))
+        );
+        assert_eq!(
+            new(&[(0, 0), (2, 0), (2, 0), (2, 2), (0, 2)]),
+            Err(PolygonError::RepeatedVertex(2))
+        );
+        assert_eq!(
+            new(&[(0, 0), (2, 0), (3, 2), (0, 2)]),
+            Err(PolygonError::NotAxisAligned(1))
+        );
+        assert_eq!(
+            new(&[(0, 0), (2, 0), (2, 2), (1, 2)]),
+            Err(PolygonError::NotClosed)
+        );
+        assert_eq!(
+            new(&[(0, 0), (1, 0), (2, 0), (2, 2), (0, 2)]),
+            Err(PolygonError::Collinear(1))
+        );
+        assert_eq!(
+            new(&[
+                (0, 2),
+                (4, 2),
+                (4, 6),
+                (2, 6),
+                (2, 0),
+                (6, 0),
+                (6, 8),
+                (0, 8)
+            ]),
+            Err(PolygonError::SelfIntersection(0, 3))
+        );
+    }
+
+    #[test]
+    fn test_error_messages() {
+        assert_eq!(
+            PolygonError::NotAxisAligned(3).to_string(),
+            "the edge from vertex 4 to vertex 5 is not horizontal or vertical"
+        );
+        assert_eq!(PolygonError::SelfIntersection(1, 5).vertex(), 5);
+    }
+
+    #[test]
+    fn test_contains_point() {
+        let polygon = u_shape();
+        let contains = |x, y| polygon.contains_point(&Point2D { x, y });
+        assert!(contains(0, 0));
+        assert!(contains(1, 3));
+        assert!(contains(4, 6));
+        assert!(contains(7, 7));
+        assert!(!contains(4, 3));
+        assert!(!contains(9, 3));
+        assert!(!contains(3, 0));
+    }
+
+    #[test]
+    fn test_contains_segment_and_rect() {
+        let polygon = u_shape();
+        let p = |x, y| Point2D { x, y };
+        assert!(polygon.contains_segment(&p(0, 7), &p(8, 7)));
+        assert!(!polygon.contains_segment(&p(0, 3), &p(8, 3)));
+        // Both ends on the boundary but the middle crosses the gap.
+        assert!(!polygon.contains_segment(&p(2, 0), &p(6, 0)));
+        assert!(polygon.contains_rect(&p(0, 6), &p(8, 8)));
+        assert!(polygon.contains_rect(&p(0, 0), &p(2, 8)));
+        assert!(!polygon.contains_rect(&p(0, 5), &p(8, 8)));
+        // The gap itself: every corner on the boundary, the inside outside.
+        assert!(!polygon.contains_rect(&p(2, 0), &p(6, 6)));
+        assert!(polygon.contains_rect(&p(2, 6), &p(6, 6)));
+    }
+}
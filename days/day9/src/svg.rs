@@ -0,0 +1,110 @@
+//! Drawing the loop and the part 2 rectangle as SVG, in the puzzle's own
+//! coordinates, for checking the geometry by eye.
+
+use std::fmt::Write;
+
+use crate::{Point2D, Rect, RectilinearPolygon, largest_rects};
+
+/// The loop filled green with its red tiles marked, the largest valid
+/// rectangle in yellow and up to `runners_up` next largest outlined in blue.
+pub fn svg(polygon: &RectilinearPolygon, runners_up: usize) -> String {
+    let vertices = polygon.vertices();
+    let min_x = vertices.iter().map(|p| p.x).min().unwrap_or(0);
+    let max_x = vertices.iter().map(|p| p.x).max().unwrap_or(0);
+    let min_y = vertices.iter().map(|p| p.y).min().unwrap_or(0);
+    let max_y = vertices.iter().map(|p| p.y).max().unwrap_or(0);
+    // Inputs span anything from tens to hundreds of thousands of tiles, so
+    // line widths and markers scale with the drawing.
+    let span = (max_x - min_x).max(max_y - min_y).max(1) as f64;
+    let line = span / 800.0;
+    let margin = span / 50.0;
+
+    let mut out = String::new();
+    let mut w = |text: String| out.push_str(&text);
+    w(format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">\n",
+        min_x as f64 - margin,
+        min_y as f64 - margin,
+        (max_x - min_x) as f64 + 2.0 * margin,
+        (max_y - min_y) as f64 + 2.0 * margin,
+    ));
+    let mut points = String::new();
+    for p in vertices {
+        write!(points, "{},{} ", p.x, p.y).expect("writing to a String cannot fail");
+    }
+    w(format!(
+        "<polygon points=\"{}\" fill=\"#2e7d32\" fill-opacity=\"0.25\" stroke=\"#2e7d32\" stroke-width=\"{line}\"/>\n",
+        points.trim_end()
+    ));
+
+    let ranked = largest_rects(polygon, runners_up + 1);
+    for ranked in ranked.iter().skip(1).rev() {
+        w(rect_element(
+            &ranked.rect,
+            ranked.area,
+            &format!(
+                "fill=\"none\" stroke=\"#1565c0\" stroke-opacity=\"0.7\" stroke-width=\"{line}\" stroke-dasharray=\"{} {}\"",
+                4.0 * line,
+                2.0 * line
+            ),
+        ));
+    }
+    if let Some(best) = ranked.first() {
+        w(rect_element(
+            &best.rect,
+            best.area,
+            &format!(
+                "fill=\"#f9a825\" fill-opacity=\"0.5\" stroke=\"#f57f17\" stroke-width=\"{}\"",
+                2.0 * line
+            ),
+        ));
+    }
+
+    for p in vertices {
+        w(format!(
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"#c62828\"/>\n",
+            p.x,
+            p.y,
+            2.0 * line
+        ));
+    }
+    w("</svg>\n".to_string());
+    out
+}
+
+fn rect_element(rect: &Rect, area: u64, style: &str) -> String {
+    let Rect { a, b } = rect;
+    let corner = Point2D {
+        x: a.x.min(b.x),
+        y: a.y.min(b.y),
+    };
+    format!(
+        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" {style}><title>{area}</title></rect>\n",
+        corner.x,
+        corner.y,
+        a.x.abs_diff(b.x),
+        a.y.abs_diff(b.y),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_input;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn test_example_drawing() {
+        let polygon = parse_input(EXAMPLE.as_bytes()).unwrap();
+        let drawing = svg(&polygon, 2);
+        assert!(drawing.starts_with("<svg "));
+        assert_eq!(
+            drawing.matches("<circle ").count(),
+            polygon.vertices().len()
+        );
+        assert_eq!(drawing.matches("<rect ").count(), 3);
+        assert!(drawing.contains("<title>24</title>"));
+        assert!(drawing.ends_with("</svg>\n"));
+    }
+}
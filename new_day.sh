@@ -1,2 +0,0 @@
-cp -r template days/$1
-cargo init days/$1
\ No newline at end of file
@@ -1,37 +1,5 @@
-use std::io::{stdin, BufRead};
+use std::process::ExitCode;
 
-fn main() {
-    let input = parse_input(stdin().lock());
-    println!("part 1: {}", part_1(&input));
-    println!("part 2: {}", part_2(&input));
-}
-
-type Input = ();
-
-fn parse_input(input: impl BufRead) -> Input {}
-
-fn part_1(input: &Input) -> u64 {
-    0
-}
-
-fn part_2(input: &Input) -> u64 {
-    0
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    const EXAMPLE: &str = include_str!("../example.txt");
-
-    #[test]
-    fn test_part_1_example() {
-        let input = parse_input(EXAMPLE.as_bytes());
-        assert_eq!(part_1(&input), 0);
-    }
-
-    #[test]
-    fn test_part_2_example() {
-        let input = parse_input(EXAMPLE.as_bytes());
-        assert_eq!(part_2(&input), 0);
-    }
+fn main() -> ExitCode {
+    aoc_common::run::<dayN::DayN>()
 }